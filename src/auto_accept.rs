@@ -0,0 +1,298 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use crate::endpoints::gameflow::GameFlowPhase;
+use crate::event_loop::{watch_uri, UriHandler};
+use crate::logging::{log_debug, log_info, log_warn};
+use crate::{Initialized, LcuDriver, Result};
+
+const GAMEFLOW_PHASE_URI: &str = "/lol-gameflow/v1/gameflow-phase";
+
+/*
+    Accepts ready checks as they pop. The gameflow phase is followed over the websocket, and when it
+    changes to `ReadyCheck` the ready check is accepted after the configured delay. The ready check is
+    read again after the delay, so one that was declined by hand or has already ended is left alone.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct AutoAccept {
+    delay: Duration,
+}
+
+impl AutoAccept {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Wait this long after the ready check pops before accepting it.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /*
+        Run until the websocket closes. Failed requests are logged and the next ready check is still
+        accepted, the ready check ending during the delay makes reading it fail for example.
+    */
+    pub async fn run(&self, driver: &LcuDriver<Initialized>) -> Result<()> {
+        watch_uri(driver, GAMEFLOW_PHASE_URI, &mut &*self).await
+    }
+
+    // Run on a background task, stop it by aborting the returned handle.
+    pub fn spawn(self, driver: Arc<LcuDriver<Initialized>>) -> JoinHandle<Result<()>> {
+        tokio::spawn(async move { self.run(&driver).await })
+    }
+
+    async fn accept(&self, driver: &LcuDriver<Initialized>) -> Result<()> {
+        if !self.delay.is_zero() {
+            tokio::time::sleep(self.delay).await;
+        }
+
+        let ready_check = driver.get_ready_check().await?;
+
+        if ready_check.awaiting_response() {
+            log_info!("Accepting ready check");
+
+            driver.accept_ready_check().await?;
+        } else {
+            log_debug!(
+                state = ?ready_check.state,
+                response = ?ready_check.player_response,
+                "Ready check no longer needs a response"
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl UriHandler for &AutoAccept {
+    type Data = GameFlowPhase;
+
+    async fn handle(
+        &mut self,
+        driver: &LcuDriver<Initialized>,
+        phase: Option<GameFlowPhase>,
+    ) -> Option<Instant> {
+        let phase = match phase {
+            Some(phase) => phase,
+            None => driver.get_gameflow_phase().await.ok()?,
+        };
+
+        if phase != GameFlowPhase::ReadyCheck {
+            return None;
+        }
+
+        #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+        if let Err(e) = self.accept(driver).await {
+            log_warn!(error = %e, "Failed to accept ready check");
+        }
+
+        None
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use reqwest::Method;
+    use serde_json::json;
+
+    use crate::auto_accept::AutoAccept;
+    use crate::testing::{FakeTransport, MockResponse};
+    use crate::websocket::LcuEventType;
+
+    fn ready_check(player_response: &str) -> MockResponse {
+        MockResponse::json(&json!({
+            "declinerIds": [],
+            "dodgeWarning": "None",
+            "playerResponse": player_response,
+            "state": "InProgress",
+            "suppressUx": false,
+            "timer": 1.0
+        }))
+    }
+
+    async fn wait_for_request(transport: &FakeTransport, method: Method, path: &str) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !transport
+                .requests()
+                .iter()
+                .any(|r| r.method == method && r.path == path)
+            {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("Request was not sent");
+    }
+
+    #[tokio::test]
+    async fn test_accepts_ready_check() {
+        let transport = FakeTransport::new();
+
+        transport.route(
+            Method::GET,
+            "/lol-gameflow/v1/gameflow-phase",
+            MockResponse::json(&"Matchmaking"),
+        );
+        transport.route(
+            Method::GET,
+            "/lol-matchmaking/v1/ready-check",
+            ready_check("None"),
+        );
+        transport.route(
+            Method::POST,
+            "/lol-matchmaking/v1/ready-check/accept",
+            MockResponse::no_content(),
+        );
+
+        let driver = transport
+            .driver_builder("./test_data/lockfile")
+            .connect()
+            .await
+            .unwrap();
+
+        let handle = AutoAccept::new()
+            .delay(Duration::from_millis(20))
+            .spawn(Arc::new(driver));
+
+        transport
+            .wait_for_subscription("OnJsonApiEvent_lol-gameflow_v1_gameflow-phase")
+            .await;
+
+        transport.push_event(
+            "/lol-gameflow/v1/gameflow-phase",
+            LcuEventType::Update,
+            &"ReadyCheck",
+        );
+
+        wait_for_request(
+            &transport,
+            Method::POST,
+            "/lol-matchmaking/v1/ready-check/accept",
+        )
+        .await;
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_leaves_answered_ready_check_alone() {
+        let transport = FakeTransport::new();
+
+        transport.route(
+            Method::GET,
+            "/lol-gameflow/v1/gameflow-phase",
+            MockResponse::json(&"ReadyCheck"),
+        );
+        transport.route_sequence(
+            Method::GET,
+            "/lol-matchmaking/v1/ready-check",
+            vec![ready_check("Declined"), ready_check("None")],
+        );
+        transport.route(
+            Method::POST,
+            "/lol-matchmaking/v1/ready-check/accept",
+            MockResponse::no_content(),
+        );
+
+        let driver = transport
+            .driver_builder("./test_data/lockfile")
+            .connect()
+            .await
+            .unwrap();
+
+        let handle = AutoAccept::new().spawn(Arc::new(driver));
+
+        transport
+            .wait_for_subscription("OnJsonApiEvent_lol-gameflow_v1_gameflow-phase")
+            .await;
+
+        // The next ready check still needs a response, so it is the one that gets accepted
+        transport.push_event(
+            "/lol-gameflow/v1/gameflow-phase",
+            LcuEventType::Update,
+            &"ReadyCheck",
+        );
+
+        wait_for_request(
+            &transport,
+            Method::POST,
+            "/lol-matchmaking/v1/ready-check/accept",
+        )
+        .await;
+
+        handle.abort();
+
+        let requests = transport.requests();
+        let accept = requests
+            .iter()
+            .position(|r| r.path == "/lol-matchmaking/v1/ready-check/accept")
+            .unwrap();
+        let ready_check_reads = requests[..accept]
+            .iter()
+            .filter(|r| r.path == "/lol-matchmaking/v1/ready-check")
+            .count();
+
+        assert_eq!(ready_check_reads, 2);
+    }
+
+    #[tokio::test]
+    async fn test_keeps_running_after_failed_request() {
+        let transport = FakeTransport::new();
+
+        transport.route(
+            Method::GET,
+            "/lol-gameflow/v1/gameflow-phase",
+            MockResponse::json(&"ReadyCheck"),
+        );
+        // The first ready check ends before it is read
+        transport.route_sequence(
+            Method::GET,
+            "/lol-matchmaking/v1/ready-check",
+            vec![
+                MockResponse::error(404, "No ready check is in progress"),
+                ready_check("None"),
+            ],
+        );
+        transport.route(
+            Method::POST,
+            "/lol-matchmaking/v1/ready-check/accept",
+            MockResponse::no_content(),
+        );
+
+        let driver = transport
+            .driver_builder("./test_data/lockfile")
+            .connect()
+            .await
+            .unwrap();
+
+        let handle = AutoAccept::new().spawn(Arc::new(driver));
+
+        transport
+            .wait_for_subscription("OnJsonApiEvent_lol-gameflow_v1_gameflow-phase")
+            .await;
+        wait_for_request(&transport, Method::GET, "/lol-matchmaking/v1/ready-check").await;
+
+        transport.push_event(
+            "/lol-gameflow/v1/gameflow-phase",
+            LcuEventType::Update,
+            &"ReadyCheck",
+        );
+
+        wait_for_request(
+            &transport,
+            Method::POST,
+            "/lol-matchmaking/v1/ready-check/accept",
+        )
+        .await;
+
+        assert!(!handle.is_finished());
+        handle.abort();
+    }
+}
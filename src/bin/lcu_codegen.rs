@@ -0,0 +1,70 @@
+/*
+    Generate endpoint bindings from a saved copy of the client's API schema, either the OpenAPI
+    dump or the output of `/help?format=Full`.
+
+    Usage: lcu_codegen <schema.json> <output directory> [crate path]
+
+    The crate path is how the generated code refers to this crate and defaults to `lcu_driver`.
+    Pass `crate` when generating into the driver itself. The output is formatted with rustfmt when
+    it is available.
+*/
+use std::path::PathBuf;
+use std::process::{Command, ExitCode};
+
+use lcu_driver::codegen::{ApiSchema, Codegen};
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let (schema_path, output) = match args.as_slice() {
+        [schema_path, output] | [schema_path, output, _] => (schema_path, PathBuf::from(output)),
+        _ => {
+            eprintln!("Usage: lcu_codegen <schema.json> <output directory> [crate path]");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let schema = match std::fs::read_to_string(schema_path)
+        .map_err(|e| e.to_string())
+        .and_then(|contents| ApiSchema::parse(&contents).map_err(|e| e.to_string()))
+    {
+        Ok(schema) => schema,
+        Err(e) => {
+            eprintln!("Failed to read schema {}: {}", schema_path, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut codegen = Codegen::new(schema);
+
+    if let Some(crate_path) = args.get(2) {
+        codegen = codegen.crate_path(crate_path);
+    }
+
+    if let Err(e) = std::fs::create_dir_all(&output) {
+        eprintln!("Failed to create {}: {}", output.display(), e);
+        return ExitCode::FAILURE;
+    }
+
+    let mut paths = Vec::new();
+
+    for file in codegen.generate() {
+        let path = output.join(&file.path);
+
+        if let Err(e) = std::fs::write(&path, file.contents) {
+            eprintln!("Failed to write {}: {}", path.display(), e);
+            return ExitCode::FAILURE;
+        }
+
+        println!("Wrote {}", path.display());
+        paths.push(path);
+    }
+
+    // Formatting is best effort, the generated code is valid either way
+    let _ = Command::new("rustfmt")
+        .args(["--edition", "2021"])
+        .args(&paths)
+        .status();
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,63 @@
+/*
+    Compare the responses of the running League client against the driver's models.
+
+    Usage: schema_drift [report.json]
+
+    The report is written to the given path, or to `schema_drift_<game version>.json` when no path
+    is given. Endpoints that are only available in certain phases, such as the champ select session,
+    are reported as errors when the client is in a different phase.
+*/
+use std::process::ExitCode;
+
+use lcu_driver::schema_drift::{detect_schema_drift, DriftIssue};
+use lcu_driver::LcuDriver;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> ExitCode {
+    let driver = match LcuDriver::connect().await {
+        Ok(driver) => driver,
+        Err(e) => {
+            eprintln!("Failed to connect to the League client: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let report = detect_schema_drift(&driver).await;
+
+    for endpoint in &report.endpoints {
+        match &endpoint.error {
+            Some(error) => println!("{} - skipped: {}", endpoint.endpoint, error),
+            None if endpoint.issues.is_empty() => println!("{} - ok", endpoint.endpoint),
+            None => println!("{} - {} issues", endpoint.endpoint, endpoint.issues.len()),
+        }
+
+        for issue in &endpoint.issues {
+            match issue {
+                DriftIssue::UnknownField { path } => println!("    unknown field {}", path),
+                DriftIssue::MissingField { path } => println!("    missing field {}", path),
+                DriftIssue::TypeMismatch {
+                    path,
+                    expected,
+                    found,
+                } => println!("    {} expected {}, found {}", path, expected, found),
+                DriftIssue::InvalidValue { path, message } => {
+                    println!("    {} {}", path, message)
+                }
+            }
+        }
+    }
+
+    let output = std::env::args().nth(1).unwrap_or_else(|| {
+        let version = report.game_version.as_deref().unwrap_or("unknown");
+        format!("schema_drift_{}.json", version)
+    });
+
+    if let Err(e) = report.save(&output).await {
+        eprintln!("Failed to save report to {}: {}", output, e);
+        return ExitCode::FAILURE;
+    }
+
+    println!("Saved report to {}", output);
+
+    ExitCode::SUCCESS
+}
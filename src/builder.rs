@@ -0,0 +1,246 @@
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderValue};
+use tokio::sync::Semaphore;
+
+use crate::interceptor::Interceptor;
+use crate::logging::{log_debug, log_info, log_trace};
+use crate::models::lcu_process::LcuProcess;
+use crate::models::lockfile::Lockfile;
+use crate::models::response_cache::{CacheInvalidator, ResponseCache, ResponseCacheConfig};
+use crate::models::single_flight::SingleFlight;
+use crate::transport::{EventTransport, HttpTransport, ReqwestTransport, TungsteniteTransport};
+use crate::{Initialized, LcuDriver, LcuDriverInner, Result};
+
+// Used unless the builder is given its own, an interceptor can ask for a retry at most twice.
+const DEFAULT_MAX_REQUEST_ATTEMPTS: usize = 3;
+const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+/*
+    Configuration for an `LcuDriver`. The builder is kept by the driver after connecting so that
+    the same configuration is used whenever the driver reconnects to a restarted client.
+*/
+#[derive(Clone, Default)]
+pub struct LcuDriverBuilder {
+    pub(crate) interceptors: Arc<Vec<Arc<dyn Interceptor>>>,
+    response_cache: Option<ResponseCacheConfig>,
+    max_concurrent_requests: Option<NonZeroUsize>,
+    max_request_attempts: Option<NonZeroUsize>,
+    retry_backoff: Option<Duration>,
+    coalesce_requests: bool,
+    root_certificate: Option<Arc<Vec<u8>>>,
+    lockfile_path: Option<PathBuf>,
+    http_transport: Option<Arc<dyn HttpTransport>>,
+    event_transport: Option<Arc<dyn EventTransport>>,
+}
+
+impl LcuDriverBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn interceptor<I: Interceptor + 'static>(mut self, interceptor: I) -> Self {
+        Arc::make_mut(&mut self.interceptors).push(Arc::new(interceptor));
+        self
+    }
+
+    // Enable caching of GET responses for the endpoints configured in `config`.
+    pub fn response_cache(mut self, config: ResponseCacheConfig) -> Self {
+        self.response_cache = Some(config);
+        self
+    }
+
+    // Limit the number of HTTP requests that can be in flight to the client at once.
+    pub fn max_concurrent_requests(mut self, max: NonZeroUsize) -> Self {
+        self.max_concurrent_requests = Some(max);
+        self
+    }
+
+    /*
+        Limit how many times a request is sent, including the first attempt, no matter how often
+        interceptors ask for it to be retried.
+    */
+    pub fn max_request_attempts(mut self, max: NonZeroUsize) -> Self {
+        self.max_request_attempts = Some(max);
+        self
+    }
+
+    // Wait this long before the first retry of a request, doubling the wait for every retry after it.
+    pub fn retry_backoff(mut self, backoff: Duration) -> Self {
+        self.retry_backoff = Some(backoff);
+        self
+    }
+
+    /*
+        Share a single response between identical GET requests that are in flight at the same time
+        instead of sending each of them to the client.
+    */
+    pub fn coalesce_requests(mut self, coalesce: bool) -> Self {
+        self.coalesce_requests = coalesce;
+        self
+    }
+
+    // Trust this PEM encoded root certificate instead of the riot games certificate.
+    pub fn root_certificate(mut self, pem: Vec<u8>) -> Self {
+        self.root_certificate = Some(Arc::new(pem));
+        self
+    }
+
+    /*
+        Read the lockfile from this path instead of locating the LeagueClientUx process. The install
+        directory is assumed to be the directory that contains the lockfile.
+    */
+    pub fn lockfile_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.lockfile_path = Some(path.into());
+        self
+    }
+
+    // Send HTTP requests through this transport instead of the default reqwest client.
+    pub fn http_transport<T: HttpTransport + 'static>(mut self, transport: T) -> Self {
+        self.http_transport = Some(Arc::new(transport));
+        self
+    }
+
+    // Open event channels through this transport instead of the default websocket.
+    pub fn event_transport<T: EventTransport + 'static>(mut self, transport: T) -> Self {
+        self.event_transport = Some(Arc::new(transport));
+        self
+    }
+
+    pub(crate) fn request_attempts_limit(&self) -> usize {
+        self.max_request_attempts
+            .map_or(DEFAULT_MAX_REQUEST_ATTEMPTS, NonZeroUsize::get)
+    }
+
+    // How long to wait before sending a request again after `attempt` failed attempts.
+    pub(crate) fn retry_delay(&self, attempt: usize) -> Duration {
+        let backoff = self.retry_backoff.unwrap_or(DEFAULT_RETRY_BACKOFF);
+        let doublings = attempt.saturating_sub(1).min(16) as u32;
+
+        backoff.saturating_mul(1 << doublings)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "lcu_discovery", skip_all, err)
+    )]
+    pub async fn connect(self) -> Result<LcuDriver<Initialized>> {
+        let cert_raw: &[u8] = match &self.root_certificate {
+            Some(cert) => cert,
+            None => include_bytes!("../certs/riotgames.pem"),
+        };
+
+        let http_transport = match &self.http_transport {
+            Some(transport) => transport.clone(),
+            None => Arc::new(ReqwestTransport::new(cert_raw)?),
+        };
+
+        let event_transport = match &self.event_transport {
+            Some(transport) => transport.clone(),
+            None => Arc::new(TungsteniteTransport::new(cert_raw)?),
+        };
+
+        let (lcu_process, lockfile_path) = match &self.lockfile_path {
+            Some(lockfile_path) => {
+                let install_directory = lockfile_path
+                    .parent()
+                    .map(Path::to_path_buf)
+                    .unwrap_or_default();
+
+                (
+                    LcuProcess::from_install_directory(install_directory),
+                    lockfile_path.clone(),
+                )
+            }
+            None => {
+                let lcu_process = LcuProcess::locate().await?;
+                let lockfile_path = lcu_process.install_directory().join("lockfile");
+
+                (lcu_process, lockfile_path)
+            }
+        };
+
+        log_debug!(install_directory = ?lcu_process.install_directory(), "Found LeagueClientUx process");
+
+        let lockfile = Lockfile::load(lockfile_path).await?;
+
+        log_debug!(port = lockfile.port, "Loaded lockfile");
+
+        let mut headers = HeaderMap::with_capacity(2);
+        headers.insert("Accept", HeaderValue::from_static("*/*"));
+        headers.insert(
+            "Authorization",
+            HeaderValue::from_str(&format!("Basic {}", lockfile.token))?,
+        );
+
+        let api_base_url = url::Url::parse(&format!("https://127.0.0.1:{}", lockfile.port))?;
+
+        let websocket_base_url = url::Url::parse(&format!("wss://localhost:{}/", lockfile.port))?;
+
+        let inner_instance = LcuDriverInner {
+            lcu_process,
+            lockfile,
+            default_req_headers: headers,
+            api_base_url,
+            websocket_base_url,
+        };
+
+        let response_cache = self
+            .response_cache
+            .clone()
+            .map(|config| Arc::new(ResponseCache::new(config)));
+
+        let cache_invalidator = response_cache.as_ref().map(|cache| {
+            CacheInvalidator::spawn(
+                cache.clone(),
+                event_transport.clone(),
+                inner_instance.websocket_base_url.clone(),
+                inner_instance.default_req_headers.clone(),
+            )
+        });
+
+        let request_limiter = self
+            .max_concurrent_requests
+            .map(|max| Semaphore::new(max.get()));
+        let single_flight = self.coalesce_requests.then(SingleFlight::new);
+
+        Ok(LcuDriver {
+            inner: tokio::sync::RwLock::new(inner_instance),
+            http_transport,
+            event_transport,
+            builder: self,
+            response_cache,
+            cache_invalidator: std::sync::Mutex::new(cache_invalidator),
+            request_limiter,
+            single_flight,
+            _state: Initialized {},
+        })
+    }
+
+    pub(crate) async fn connect_wait_no_reconnect(&self) -> LcuDriver<Initialized> {
+        loop {
+            if let Ok(lcu_driver) = self.clone().connect().await {
+                //Check that we can actually connect to the client
+                if lcu_driver.get_current_summoner().await.is_ok() {
+                    log_info!("Connected to the League client");
+
+                    return lcu_driver;
+                }
+            }
+
+            log_trace!("League client is not ready, retrying");
+
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+    }
+
+    pub async fn connect_wait(self) -> Arc<LcuDriver<Initialized>> {
+        let pointer = Arc::new(self.connect_wait_no_reconnect().await);
+        LcuDriver::start_lockfile_watching(pointer.clone());
+
+        pointer
+    }
+}
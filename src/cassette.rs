@@ -0,0 +1,405 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tokio::fs::File;
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::sync::{mpsc, oneshot};
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::endpoints::EndpointInfo;
+use crate::errors::LcuDriverError;
+use crate::interceptor::{Interceptor, ResponseInfo};
+use crate::logging::log_warn;
+use crate::websocket::{LcuEvent, LcuWebSocket};
+use crate::Result;
+
+/*
+    A recording of a session with the client. Cassettes are stored as JSONL, one entry per line,
+    so that a recording that was cut short is still readable up to the last complete line.
+*/
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Cassette {
+    pub entries: Vec<CassetteEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum CassetteEntry {
+    #[serde(rename_all = "camelCase")]
+    Http {
+        // Milliseconds since the unix epoch
+        timestamp: u64,
+        method: String,
+        path: String,
+        request_body: Option<String>,
+        status: u16,
+        response_body: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Event { timestamp: u64, event: LcuEvent },
+    // A request that failed without a response, e.g. because the client could not be reached
+    #[serde(rename_all = "camelCase")]
+    Error {
+        timestamp: u64,
+        method: String,
+        path: String,
+        request_body: Option<String>,
+        error: String,
+    },
+}
+
+impl CassetteEntry {
+    pub fn timestamp(&self) -> u64 {
+        match self {
+            CassetteEntry::Http { timestamp, .. }
+            | CassetteEntry::Event { timestamp, .. }
+            | CassetteEntry::Error { timestamp, .. } => *timestamp,
+        }
+    }
+}
+
+impl Cassette {
+    pub fn from_jsonl(contents: &str) -> Result<Self> {
+        let entries = contents
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<std::result::Result<_, _>>()?;
+
+        Ok(Self { entries })
+    }
+
+    pub fn to_jsonl(&self) -> Result<String> {
+        let mut contents = String::new();
+
+        for entry in &self.entries {
+            contents.push_str(&serde_json::to_string(entry)?);
+            contents.push('\n');
+        }
+
+        Ok(contents)
+    }
+
+    pub async fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let contents = tokio::fs::read_to_string(path).await?;
+
+        Self::from_jsonl(&contents)
+    }
+
+    pub async fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        tokio::fs::write(path, self.to_jsonl()?).await?;
+
+        Ok(())
+    }
+
+    pub fn events(&self) -> impl Iterator<Item = &LcuEvent> {
+        self.entries.iter().filter_map(|entry| match entry {
+            CassetteEntry::Event { event, .. } => Some(event),
+            CassetteEntry::Http { .. } | CassetteEntry::Error { .. } => None,
+        })
+    }
+}
+
+/*
+    An interceptor that appends every HTTP response, failed request and websocket event the driver sees
+    to a cassette file. Streamed responses are left out, as their bodies are never read and could not be
+    replayed. Register it with `LcuDriverBuilder::interceptor`. Entries are written by a
+    background task so recording never blocks the driver, and are flushed whenever the task has caught
+    up, so the cassette survives the process being killed. Use `flush` to wait for the file to be
+    up to date.
+*/
+pub struct CassetteRecorder {
+    sender: mpsc::UnboundedSender<WriterCommand>,
+    // Error responses that have been recorded but not yet reported to `on_error`
+    error_responses: Mutex<HashMap<RequestKey, usize>>,
+}
+
+type RequestKey = (String, String, Option<String>);
+
+enum WriterCommand {
+    Record(CassetteEntry),
+    Flush(oneshot::Sender<()>),
+}
+
+impl CassetteRecorder {
+    pub async fn create<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = File::create(path).await?;
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        tokio::spawn(Self::write_entries(BufWriter::new(file), receiver));
+
+        Ok(Self {
+            sender,
+            error_responses: Mutex::new(HashMap::new()),
+        })
+    }
+
+    // Wait until every entry recorded so far has been written to the file.
+    pub async fn flush(&self) -> Result<()> {
+        let (done, flushed) = oneshot::channel();
+
+        self.sender
+            .send(WriterCommand::Flush(done))
+            .map_err(|_| LcuDriverError::new("Cassette writer has stopped"))?;
+
+        flushed
+            .await
+            .map_err(|_| LcuDriverError::new("Cassette writer has stopped"))
+    }
+
+    fn record(&self, entry: CassetteEntry) {
+        // Only fails once the writer has stopped, which has already been logged
+        let _ = self.sender.send(WriterCommand::Record(entry));
+    }
+
+    // Runs until the recorder is dropped and every entry sent before then has been written.
+    async fn write_entries(
+        mut writer: BufWriter<File>,
+        mut receiver: mpsc::UnboundedReceiver<WriterCommand>,
+    ) {
+        while let Some(command) = receiver.recv().await {
+            let mut next = Some(command);
+            let mut result = Ok(());
+
+            // Write everything that is queued and flush once, rather than after every entry
+            while let Some(command) = next.take() {
+                result = match command {
+                    WriterCommand::Record(entry) => Self::write_entry(&mut writer, &entry).await,
+                    WriterCommand::Flush(done) => {
+                        // Dropping `done` on failure tells the caller the writer has stopped
+                        let result = writer.flush().await;
+                        if result.is_ok() {
+                            let _ = done.send(());
+                        }
+                        result
+                    }
+                };
+
+                if result.is_ok() {
+                    next = receiver.try_recv().ok();
+                }
+            }
+
+            if result.is_ok() {
+                result = writer.flush().await;
+            }
+
+            #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+            if let Err(e) = result {
+                log_warn!(error = %e, "Failed to write cassette, stopping the recording");
+                return;
+            }
+        }
+    }
+
+    async fn write_entry(
+        writer: &mut BufWriter<File>,
+        entry: &CassetteEntry,
+    ) -> std::io::Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+
+        writer.write_all(&line).await
+    }
+
+    fn request_key(endpoint: &EndpointInfo) -> RequestKey {
+        (
+            endpoint.method.to_string(),
+            endpoint.url.clone(),
+            endpoint.body.clone(),
+        )
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default()
+    }
+}
+
+impl Interceptor for CassetteRecorder {
+    fn on_response(&self, endpoint: &EndpointInfo, response: &ResponseInfo) {
+        if response.body.is_none() {
+            return;
+        }
+
+        // The driver turns every error response into an error, which must not be recorded twice
+        if !response.status.is_success() {
+            *self
+                .error_responses
+                .lock()
+                .expect("Cassette error responses lock poisoned")
+                .entry(Self::request_key(endpoint))
+                .or_default() += 1;
+        }
+
+        self.record(CassetteEntry::Http {
+            timestamp: Self::now(),
+            method: endpoint.method.to_string(),
+            path: endpoint.url.clone(),
+            request_body: endpoint.body.clone(),
+            status: response.status.as_u16(),
+            response_body: response.body.map(str::to_owned),
+        });
+    }
+
+    fn on_error(&self, endpoint: &EndpointInfo, error: &LcuDriverError, _elapsed: Duration) {
+        let key = Self::request_key(endpoint);
+        let mut error_responses = self
+            .error_responses
+            .lock()
+            .expect("Cassette error responses lock poisoned");
+
+        // Errors that follow an error response have already been recorded by `on_response`
+        if let Some(count) = error_responses.get_mut(&key) {
+            *count -= 1;
+
+            if *count == 0 {
+                error_responses.remove(&key);
+            }
+
+            return;
+        }
+
+        drop(error_responses);
+
+        self.record(CassetteEntry::Error {
+            timestamp: Self::now(),
+            method: endpoint.method.to_string(),
+            path: endpoint.url.clone(),
+            request_body: endpoint.body.clone(),
+            error: error.to_string(),
+        });
+    }
+
+    fn on_websocket_receive(&self, message: &Message) {
+        if let Message::Text(text) = message {
+            if let Ok(event) = LcuWebSocket::parse_event(text) {
+                self.record(CassetteEntry::Event {
+                    timestamp: Self::now(),
+                    event,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use std::time::Duration;
+
+    use reqwest::{Method, StatusCode};
+
+    use crate::cassette::{Cassette, CassetteEntry, CassetteRecorder};
+    use crate::endpoints::EndpointInfo;
+    use crate::errors::LcuDriverError;
+    use crate::interceptor::{Interceptor, ResponseInfo};
+    use crate::websocket::{LcuEvent, LcuEventType};
+
+    #[test]
+    fn test_jsonl_round_trip() {
+        let cassette = Cassette {
+            entries: vec![
+                CassetteEntry::Http {
+                    timestamp: 1655000000000,
+                    method: "GET".to_owned(),
+                    path: "/lol-gameflow/v1/gameflow-phase".to_owned(),
+                    request_body: None,
+                    status: 200,
+                    response_body: Some("\"Lobby\"".to_owned()),
+                },
+                CassetteEntry::Event {
+                    timestamp: 1655000001500,
+                    event: LcuEvent {
+                        data: json!("Matchmaking"),
+                        event_type: LcuEventType::Update,
+                        uri: "/lol-gameflow/v1/gameflow-phase".to_owned(),
+                    },
+                },
+            ],
+        };
+
+        let jsonl = cassette.to_jsonl().expect("Failed to serialize cassette");
+
+        assert_eq!(jsonl.lines().count(), 2);
+        assert_eq!(
+            Cassette::from_jsonl(&jsonl).expect("Failed to parse cassette"),
+            cassette
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recorder_writes_responses_and_transport_errors() {
+        let path =
+            std::env::temp_dir().join(format!("lcu_driver_cassette_{}.jsonl", std::process::id()));
+        let recorder = CassetteRecorder::create(&path)
+            .await
+            .expect("Failed to create cassette recorder");
+        let endpoint = EndpointInfo {
+            url: "/lol-summoner/v1/current-summoner".to_owned(),
+            method: Method::GET,
+            headers: None,
+            body: None,
+        };
+
+        recorder.on_response(
+            &endpoint,
+            &ResponseInfo {
+                status: StatusCode::OK,
+                elapsed: Duration::ZERO,
+                body: Some("{}"),
+            },
+        );
+        recorder.on_error(
+            &endpoint,
+            &LcuDriverError::FailedToSendRequest("connection refused".to_owned()),
+            Duration::ZERO,
+        );
+        // Streamed responses are left out
+        recorder.on_response(
+            &endpoint,
+            &ResponseInfo {
+                status: StatusCode::OK,
+                elapsed: Duration::ZERO,
+                body: None,
+            },
+        );
+        // An error response whose body is not an API error is only recorded as the response
+        recorder.on_response(
+            &endpoint,
+            &ResponseInfo {
+                status: StatusCode::BAD_GATEWAY,
+                elapsed: Duration::ZERO,
+                body: Some("Bad Gateway"),
+            },
+        );
+        recorder.on_error(
+            &endpoint,
+            &LcuDriverError::FailedToReadResponse("expected value".to_owned()),
+            Duration::ZERO,
+        );
+        recorder.flush().await.expect("Failed to flush cassette");
+
+        let cassette = Cassette::load(&path)
+            .await
+            .expect("Failed to load cassette");
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(cassette.entries.len(), 3);
+        assert!(matches!(
+            &cassette.entries[1],
+            CassetteEntry::Error { path, error, .. }
+                if path == "/lol-summoner/v1/current-summoner" && error.contains("connection refused")
+        ));
+        assert!(matches!(
+            &cassette.entries[2],
+            CassetteEntry::Http { status: 502, .. }
+        ));
+    }
+}
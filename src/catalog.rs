@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use crate::endpoints::game_data::{
+    ChampionSummary, GameDataEndpoint, PerkData, PerkStyle, PerkStyles, SummonerSpellData,
+};
+use crate::endpoints::ids::{ChampionId, PerkId, PerkStyleId, SpellId};
+use crate::logging::log_debug;
+use crate::{Initialized, LcuDriver, Result};
+
+pub trait CatalogEntry {
+    type Id: Copy + Eq + Hash;
+
+    fn id(&self) -> Self::Id;
+
+    fn name(&self) -> &str;
+
+    fn alias(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl CatalogEntry for ChampionSummary {
+    type Id = ChampionId;
+
+    fn id(&self) -> ChampionId {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn alias(&self) -> Option<&str> {
+        Some(&self.alias)
+    }
+}
+
+impl CatalogEntry for SummonerSpellData {
+    type Id = SpellId;
+
+    fn id(&self) -> SpellId {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl CatalogEntry for PerkData {
+    type Id = PerkId;
+
+    fn id(&self) -> PerkId {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl CatalogEntry for PerkStyle {
+    type Id = PerkStyleId;
+
+    fn id(&self) -> PerkStyleId {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/*
+    Game data entries looked up by ID, or case-insensitively by name or alias. When a name, alias or
+    ID is repeated, lookups return the first matching entry in the order they were given, and a name
+    takes precedence over another entry's alias.
+*/
+#[derive(Debug, Clone)]
+pub struct Catalog<T: CatalogEntry> {
+    entries: Vec<T>,
+    by_id: HashMap<T::Id, usize>,
+    by_name: HashMap<String, usize>,
+}
+
+impl<T: CatalogEntry> Catalog<T> {
+    pub fn new(entries: Vec<T>) -> Self {
+        let mut by_id = HashMap::with_capacity(entries.len());
+        let mut by_name = HashMap::with_capacity(entries.len());
+
+        for (index, entry) in entries.iter().enumerate() {
+            by_id.entry(entry.id()).or_insert(index);
+            by_name.entry(entry.name().to_lowercase()).or_insert(index);
+        }
+
+        // Aliases go in after every name, so that they never hide one
+        for (index, entry) in entries.iter().enumerate() {
+            if let Some(alias) = entry.alias() {
+                by_name.entry(alias.to_lowercase()).or_insert(index);
+            }
+        }
+
+        Self {
+            entries,
+            by_id,
+            by_name,
+        }
+    }
+
+    pub fn get(&self, id: T::Id) -> Option<&T> {
+        self.by_id.get(&id).map(|&index| &self.entries[index])
+    }
+
+    // The first entry with this name, or failing that the first entry with this alias.
+    pub fn find(&self, name_or_alias: &str) -> Option<&T> {
+        self.by_name
+            .get(&name_or_alias.to_lowercase())
+            .map(|&index| &self.entries[index])
+    }
+
+    pub fn name_of(&self, id: T::Id) -> Option<&str> {
+        self.get(id).map(|entry| entry.name())
+    }
+
+    pub fn id_of(&self, name_or_alias: &str) -> Option<T::Id> {
+        self.find(name_or_alias).map(|entry| entry.id())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GameDataCatalogs {
+    // The client version the game data was loaded from
+    pub client_version: String,
+    pub champions: Catalog<ChampionSummary>,
+    pub summoner_spells: Catalog<SummonerSpellData>,
+    pub perks: Catalog<PerkData>,
+    pub perk_styles: Catalog<PerkStyle>,
+}
+
+impl GameDataCatalogs {
+    pub async fn load(driver: &LcuDriver<Initialized>, client_version: String) -> Result<Self> {
+        let (champions, summoner_spells, perks, perk_styles) = tokio::try_join!(
+            driver.get_and_deserialize_endpoint(GameDataEndpoint::ChampionSummary.info()),
+            driver.get_and_deserialize_endpoint(GameDataEndpoint::SummonerSpells.info()),
+            driver.get_and_deserialize_endpoint(GameDataEndpoint::Perks.info()),
+            driver.get_and_deserialize_endpoint::<PerkStyles>(GameDataEndpoint::PerkStyles.info()),
+        )?;
+
+        Ok(Self {
+            client_version,
+            champions: Catalog::new(champions),
+            summoner_spells: Catalog::new(summoner_spells),
+            perks: Catalog::new(perks),
+            perk_styles: Catalog::new(perk_styles.styles),
+        })
+    }
+}
+
+/*
+    Keeps the catalogs for the running client version in memory. The game data only changes with a
+    patch, so the client version is checked on every call and the catalogs are reloaded when it moves.
+*/
+#[derive(Debug, Default)]
+pub struct CatalogCache {
+    current: Mutex<Option<Arc<GameDataCatalogs>>>,
+}
+
+impl CatalogCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn get(&self, driver: &LcuDriver<Initialized>) -> Result<Arc<GameDataCatalogs>> {
+        // Held while loading so concurrent callers wait for one load instead of starting their own
+        let mut current = self.current.lock().await;
+        let client_version = driver.get_game_version().await?;
+
+        if let Some(catalogs) = current
+            .as_ref()
+            .filter(|c| c.client_version == client_version)
+        {
+            return Ok(catalogs.clone());
+        }
+
+        log_debug!(%client_version, "Loading game data catalogs");
+
+        let catalogs = Arc::new(GameDataCatalogs::load(driver, client_version).await?);
+        *current = Some(catalogs.clone());
+
+        Ok(catalogs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::catalog::Catalog;
+    use crate::endpoints::fixtures::{assert_round_trip, load_fixture};
+    use crate::endpoints::game_data::{ChampionSummary, PerkStyles, SummonerSpellData};
+    use crate::endpoints::ids::{ChampionId, PerkStyleId, SpellId};
+
+    #[test]
+    fn test_champion_lookups() {
+        let champions = Catalog::new(assert_round_trip::<Vec<ChampionSummary>>(
+            "game_data/champion_summary.json",
+        ));
+
+        assert_eq!(champions.len(), 4);
+        assert_eq!(champions.name_of(ChampionId(62)), Some("Wukong"));
+        assert_eq!(champions.id_of("wukong"), Some(ChampionId(62)));
+        assert_eq!(champions.id_of("MonkeyKing"), Some(ChampionId(62)));
+        assert!(champions.get(ChampionId(999)).is_none());
+    }
+
+    #[test]
+    fn test_duplicate_names_keep_the_first_entry() {
+        let mut value = load_fixture("game_data/champion_summary.json");
+        let entries = value.as_array_mut().unwrap();
+
+        // A second Wukong, and a champion whose alias is Ahri's name
+        let mut duplicate = entries[2].clone();
+        duplicate["id"] = json!(9062);
+        entries.push(duplicate);
+
+        let mut aliased = entries[1].clone();
+        aliased["id"] = json!(9001);
+        aliased["name"] = json!("Not Ahri");
+        aliased["alias"] = json!("Ahri");
+        entries.insert(0, aliased);
+
+        let champions: Catalog<ChampionSummary> =
+            Catalog::new(serde_json::from_value(value).unwrap());
+
+        assert_eq!(champions.id_of("Wukong"), Some(ChampionId(62)));
+        assert_eq!(champions.id_of("MonkeyKing"), Some(ChampionId(62)));
+        assert_eq!(champions.id_of("Ahri"), Some(ChampionId(103)));
+        assert_eq!(champions.name_of(ChampionId(9062)), Some("Wukong"));
+    }
+
+    #[test]
+    fn test_spell_and_style_lookups() {
+        let spells = Catalog::new(assert_round_trip::<Vec<SummonerSpellData>>(
+            "game_data/summoner_spells.json",
+        ));
+        let styles =
+            Catalog::new(assert_round_trip::<PerkStyles>("game_data/perkstyles.json").styles);
+
+        assert_eq!(spells.id_of("Flash"), Some(SpellId(4)));
+        assert_eq!(spells.find("mark").map(|s| s.cooldown), Some(80));
+        assert_eq!(styles.name_of(PerkStyleId(8100)), Some("Domination"));
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod driver_tests {
+    use std::sync::Arc;
+
+    use reqwest::Method;
+
+    use crate::catalog::CatalogCache;
+    use crate::endpoints::fixtures::load_fixture;
+    use crate::endpoints::ids::ChampionId;
+    use crate::testing::{FakeTransport, MockResponse};
+
+    fn game_data_requests(transport: &FakeTransport) -> usize {
+        transport
+            .requests()
+            .iter()
+            .filter(|r| r.path == "/lol-game-data/assets/v1/champion-summary.json")
+            .count()
+    }
+
+    #[tokio::test]
+    async fn test_reloads_on_new_client_version() {
+        let transport = FakeTransport::new();
+
+        transport.route_sequence(
+            Method::GET,
+            "/lol-patch/v1/game-version",
+            vec![
+                MockResponse::json(&"12.10.1"),
+                MockResponse::json(&"12.10.1"),
+                MockResponse::json(&"12.11.1"),
+            ],
+        );
+
+        for (path, file) in [
+            ("champion-summary.json", "champion_summary.json"),
+            ("summoner-spells.json", "summoner_spells.json"),
+            ("perks.json", "perks.json"),
+            ("perkstyles.json", "perkstyles.json"),
+        ] {
+            transport.route(
+                Method::GET,
+                &format!("/lol-game-data/assets/v1/{}", path),
+                MockResponse::json(&load_fixture(&format!("game_data/{}", file))),
+            );
+        }
+
+        let driver = transport
+            .driver_builder("./test_data/lockfile")
+            .connect()
+            .await
+            .unwrap();
+
+        let cache = CatalogCache::new();
+
+        let first = cache.get(&driver).await.unwrap();
+        let second = cache.get(&driver).await.unwrap();
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(game_data_requests(&transport), 1);
+
+        let patched = cache.get(&driver).await.unwrap();
+
+        assert_eq!(patched.client_version, "12.11.1");
+        assert_eq!(patched.champions.id_of("Ahri"), Some(ChampionId(103)));
+        assert_eq!(game_data_requests(&transport), 2);
+    }
+}
@@ -0,0 +1,629 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use crate::champ_select_tracker::CHAMP_SELECT_SESSION_URI;
+use crate::endpoints::champ_select::{
+    ActionType, ActionUpdate, ChampSelectPhase, ChampSelectSession, ChampionAvailability,
+};
+use crate::endpoints::ids::ChampionId;
+use crate::endpoints::lobby::LobbyPosition;
+use crate::event_loop::{watch_uri, UriHandler};
+use crate::logging::{log_debug, log_info, log_warn};
+use crate::{Initialized, LcuDriver, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutomationAction {
+    Hover {
+        action_id: isize,
+        champion_id: ChampionId,
+    },
+    LockIn {
+        action_id: isize,
+        champion_id: ChampionId,
+    },
+}
+
+impl AutomationAction {
+    pub fn action_id(&self) -> isize {
+        match self {
+            AutomationAction::Hover { action_id, .. }
+            | AutomationAction::LockIn { action_id, .. } => *action_id,
+        }
+    }
+
+    fn update(&self) -> ActionUpdate {
+        match *self {
+            AutomationAction::Hover { champion_id, .. } => ActionUpdate::hover(champion_id),
+            AutomationAction::LockIn { champion_id, .. } => ActionUpdate::lock_in(champion_id),
+        }
+    }
+}
+
+// What to do about one session, and when to look again if the lock in is still waiting on the timer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AutomationStep {
+    pub action: Option<AutomationAction>,
+    pub recheck_in: Option<Duration>,
+}
+
+/*
+    Picks and bans champions from priority lists. Each position has its own lists, falling back to the
+    default lists when a position has none or the queue has no positions. Only champions the client offers
+    are considered, see `ChampionAvailability`, and bans skip champions our team is hovering or playing as
+    well as the champion we would pick.
+
+    The first available champion is hovered when our turn starts, optionally already during planning, and
+    locked in either straight away or once `lock_in_before_end` is left on the timer. In dry-run mode the
+    decisions are only logged.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct ChampSelectAutomation {
+    picks: HashMap<LobbyPosition, Vec<ChampionId>>,
+    bans: HashMap<LobbyPosition, Vec<ChampionId>>,
+    default_picks: Vec<ChampionId>,
+    default_bans: Vec<ChampionId>,
+    hover_during_planning: bool,
+    lock_in_before_end: Option<Duration>,
+    dry_run: bool,
+    last_action: Option<AutomationAction>,
+}
+
+impl ChampSelectAutomation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn picks(mut self, position: LobbyPosition, champion_ids: Vec<ChampionId>) -> Self {
+        self.picks.insert(position, champion_ids);
+        self
+    }
+
+    pub fn bans(mut self, position: LobbyPosition, champion_ids: Vec<ChampionId>) -> Self {
+        self.bans.insert(position, champion_ids);
+        self
+    }
+
+    pub fn default_picks(mut self, champion_ids: Vec<ChampionId>) -> Self {
+        self.default_picks = champion_ids;
+        self
+    }
+
+    pub fn default_bans(mut self, champion_ids: Vec<ChampionId>) -> Self {
+        self.default_bans = champion_ids;
+        self
+    }
+
+    pub fn hover_during_planning(mut self, hover: bool) -> Self {
+        self.hover_during_planning = hover;
+        self
+    }
+
+    // Wait until this much time is left in the turn before locking in, instead of locking in right away.
+    pub fn lock_in_before_end(mut self, time_left: Duration) -> Self {
+        self.lock_in_before_end = Some(time_left);
+        self
+    }
+
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /*
+        Decide what to do about a session. An action that was already returned for the previous session
+        is not returned again, which keeps dry runs from repeating themselves while the session catches up.
+    */
+    pub fn update(&mut self, availability: &ChampionAvailability) -> AutomationStep {
+        let mut step = self.plan(availability);
+
+        if step.action.is_some() && step.action == self.last_action {
+            step.action = None;
+        } else if step.action.is_some() {
+            self.last_action = step.action;
+        }
+
+        step
+    }
+
+    fn plan(&self, availability: &ChampionAvailability) -> AutomationStep {
+        let session = &availability.session;
+
+        if session.timer.phase == ChampSelectPhase::Planning {
+            return AutomationStep {
+                action: self.plan_planning_hover(availability),
+                recheck_in: None,
+            };
+        }
+
+        let action = match session.current_action() {
+            Some(action) => action,
+            None => return AutomationStep::default(),
+        };
+
+        let champion_id = match action.type_field {
+            ActionType::Pick => self.pick_candidate(availability),
+            ActionType::Ban => self.ban_candidate(availability),
+            _ => None,
+        };
+
+        let champion_id = match champion_id {
+            Some(champion_id) => champion_id,
+            None => return AutomationStep::default(),
+        };
+
+        let time_left =
+            Duration::from_millis(session.timer.adjusted_time_left_in_phase.max(0) as u64);
+
+        match self.lock_in_before_end {
+            Some(lock_in_at) if !session.timer.is_infinite && time_left > lock_in_at => {
+                AutomationStep {
+                    action: (action.champion_id != champion_id).then_some(
+                        AutomationAction::Hover {
+                            action_id: action.id,
+                            champion_id,
+                        },
+                    ),
+                    recheck_in: Some(time_left - lock_in_at),
+                }
+            }
+            _ => AutomationStep {
+                action: Some(AutomationAction::LockIn {
+                    action_id: action.id,
+                    champion_id,
+                }),
+                recheck_in: None,
+            },
+        }
+    }
+
+    fn plan_planning_hover(&self, availability: &ChampionAvailability) -> Option<AutomationAction> {
+        if !self.hover_during_planning {
+            return None;
+        }
+
+        let action = availability
+            .session
+            .local_player_actions()
+            .find(|a| a.type_field == ActionType::Pick && !a.completed)?;
+        let champion_id = self.pick_candidate(availability)?;
+
+        (action.champion_id != champion_id).then_some(AutomationAction::Hover {
+            action_id: action.id,
+            champion_id,
+        })
+    }
+
+    fn priorities<'a>(
+        &'a self,
+        lists: &'a HashMap<LobbyPosition, Vec<ChampionId>>,
+        defaults: &'a [ChampionId],
+        session: &ChampSelectSession,
+    ) -> &'a [ChampionId] {
+        let position = session
+            .local_player_selection()
+            .map(|p| p.position())
+            .unwrap_or(LobbyPosition::Unselected);
+
+        lists.get(&position).map(Vec::as_slice).unwrap_or(defaults)
+    }
+
+    fn pick_candidate(&self, availability: &ChampionAvailability) -> Option<ChampionId> {
+        self.priorities(&self.picks, &self.default_picks, &availability.session)
+            .iter()
+            .copied()
+            .find(|&id| availability.is_pickable(id))
+    }
+
+    fn ban_candidate(&self, availability: &ChampionAvailability) -> Option<ChampionId> {
+        let session = &availability.session;
+        let wanted: Vec<ChampionId> = session
+            .my_team
+            .iter()
+            .flat_map(|p| [p.champion_id, p.champion_pick_intent])
+            .chain(self.pick_candidate(availability))
+            .collect();
+
+        self.priorities(&self.bans, &self.default_bans, session)
+            .iter()
+            .copied()
+            .find(|&id| availability.is_bannable(id) && !wanted.contains(&id))
+    }
+
+    /*
+        Run until the websocket closes. Failed requests are logged and tried again on the next session
+        update, so champ select ending just before a recheck does not stop the engine.
+    */
+    pub async fn run(&mut self, driver: &LcuDriver<Initialized>) -> Result<()> {
+        let mut run = AutomationRun {
+            automation: self,
+            availability: None,
+        };
+
+        watch_uri(driver, CHAMP_SELECT_SESSION_URI, &mut run).await
+    }
+
+    // Run on a background task, stop it by aborting the returned handle.
+    pub fn spawn(mut self, driver: Arc<LcuDriver<Initialized>>) -> JoinHandle<Result<()>> {
+        tokio::spawn(async move { self.run(&driver).await })
+    }
+}
+
+// What `run` keeps for the current champ select. The champion lists are only read once per champ select.
+struct AutomationRun<'a> {
+    automation: &'a mut ChampSelectAutomation,
+    availability: Option<ChampionAvailability>,
+}
+
+impl UriHandler for AutomationRun<'_> {
+    type Data = ChampSelectSession;
+
+    async fn handle(
+        &mut self,
+        driver: &LcuDriver<Initialized>,
+        session: Option<ChampSelectSession>,
+    ) -> Option<Instant> {
+        let result = match (self.availability.as_mut(), session) {
+            (Some(availability), Some(session)) => {
+                availability.session = session;
+                Ok(())
+            }
+            (Some(availability), None) => driver
+                .get_champ_select_session()
+                .await
+                .map(|session| availability.session = session),
+            (None, _) => driver
+                .get_champion_availability()
+                .await
+                .map(|availability| self.availability = Some(availability)),
+        };
+
+        // Outside of champ select reading it fails
+        #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+        if let Err(e) = result {
+            log_debug!(error = %e, "Failed to read champ select");
+            return None;
+        }
+
+        let availability = self.availability.as_ref()?;
+        let step = self.automation.update(availability);
+
+        if let Some(action) = step.action {
+            if self.automation.dry_run {
+                log_info!(action = ?action, "Dry run, not acting on champ select");
+            } else {
+                log_debug!(action = ?action, "Acting on champ select");
+
+                #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+                if let Err(e) = driver
+                    .update_champ_select_action(action.action_id(), &action.update())
+                    .await
+                {
+                    log_warn!(error = %e, action = ?action, "Failed to act on champ select");
+
+                    // Try again with the next session
+                    self.automation.last_action = None;
+                }
+            }
+        }
+
+        step.recheck_in.map(|delay| Instant::now() + delay)
+    }
+
+    fn reset(&mut self) {
+        self.automation.last_action = None;
+        self.availability = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::{json, Value};
+
+    use crate::champ_select_automation::{AutomationAction, AutomationStep, ChampSelectAutomation};
+    use crate::endpoints::champ_select::ChampionAvailability;
+    use crate::endpoints::fixtures::{load_fixture, session_action};
+    use crate::endpoints::ids::ChampionId;
+    use crate::endpoints::lobby::LobbyPosition;
+
+    const OWNED: [i32; 12] = [1, 11, 22, 51, 64, 86, 89, 103, 121, 122, 157, 266];
+
+    fn fixture() -> Value {
+        load_fixture("champ_select/ranked_draft.json")
+    }
+
+    fn availability(value: &Value) -> ChampionAvailability {
+        ChampionAvailability {
+            session: serde_json::from_value(value.clone()).unwrap(),
+            pickable_champion_ids: OWNED.map(ChampionId).to_vec(),
+            bannable_champion_ids: OWNED.map(ChampionId).to_vec(),
+            disabled_champion_ids: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_scripted_pick() {
+        // The local player is in cell 1 and plays jungle
+        let mut automation = ChampSelectAutomation::new()
+            .picks(
+                LobbyPosition::Jungle,
+                vec![
+                    ChampionId(64),
+                    ChampionId(266),
+                    ChampionId(86),
+                    ChampionId(11),
+                    ChampionId(121),
+                ],
+            )
+            .default_picks(vec![ChampionId(1)])
+            .hover_during_planning(true)
+            .lock_in_before_end(Duration::from_secs(10));
+
+        let mut value = fixture();
+        value["timer"]["phase"] = json!("PLANNING");
+
+        // 64 is banned, 266 and 86 are locked in by others
+        assert_eq!(
+            automation.update(&availability(&value)),
+            AutomationStep {
+                action: Some(AutomationAction::Hover {
+                    action_id: 14,
+                    champion_id: ChampionId(11)
+                }),
+                recheck_in: None
+            }
+        );
+
+        value["timer"]["phase"] = json!("BAN_PICK");
+        session_action(&mut value, 13)["completed"] = json!(true);
+        session_action(&mut value, 13)["isInProgress"] = json!(false);
+        session_action(&mut value, 13)["championId"] = json!(11);
+        session_action(&mut value, 14)["isInProgress"] = json!(true);
+        value["timer"]["adjustedTimeLeftInPhase"] = json!(30000);
+
+        // Our hover was taken, move on to the next champion and wait for the timer
+        assert_eq!(
+            automation.update(&availability(&value)),
+            AutomationStep {
+                action: Some(AutomationAction::Hover {
+                    action_id: 14,
+                    champion_id: ChampionId(121)
+                }),
+                recheck_in: Some(Duration::from_secs(20))
+            }
+        );
+
+        // Nothing new to do until the hover shows up in the session
+        assert_eq!(automation.update(&availability(&value)).action, None);
+
+        session_action(&mut value, 14)["championId"] = json!(121);
+        value["timer"]["adjustedTimeLeftInPhase"] = json!(9500);
+
+        assert_eq!(
+            automation.update(&availability(&value)),
+            AutomationStep {
+                action: Some(AutomationAction::LockIn {
+                    action_id: 14,
+                    champion_id: ChampionId(121)
+                }),
+                recheck_in: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_skips_champions_that_are_not_offered() {
+        let mut automation = ChampSelectAutomation::new()
+            .default_picks(vec![ChampionId(11), ChampionId(121)])
+            .hover_during_planning(true);
+
+        let mut value = fixture();
+        value["timer"]["phase"] = json!("PLANNING");
+
+        let mut availability = availability(&value);
+        availability
+            .pickable_champion_ids
+            .retain(|&id| id != ChampionId(11));
+
+        assert_eq!(
+            automation.update(&availability).action,
+            Some(AutomationAction::Hover {
+                action_id: 14,
+                champion_id: ChampionId(121)
+            })
+        );
+    }
+
+    #[test]
+    fn test_scripted_ban() {
+        let mut automation = ChampSelectAutomation::new()
+            .default_bans(vec![
+                ChampionId(157),
+                ChampionId(103),
+                ChampionId(89),
+                ChampionId(122),
+                ChampionId(51),
+                ChampionId(22),
+                ChampionId(1),
+            ])
+            .default_picks(vec![ChampionId(22)]);
+
+        let mut value = fixture();
+        session_action(&mut value, 1)["completed"] = json!(false);
+        session_action(&mut value, 1)["isInProgress"] = json!(true);
+        session_action(&mut value, 1)["championId"] = json!(0);
+        session_action(&mut value, 13)["isInProgress"] = json!(false);
+        value["bans"]["myTeamBans"] = json!([157, 777, 350]);
+        value["myTeam"][1]["championPickIntent"] = json!(51);
+
+        /*
+            157 and 122 are banned, 103 and 89 are hovered by allies, 51 is our own hover and 22 is the
+            champion we would pick
+        */
+        assert_eq!(
+            automation.update(&availability(&value)).action,
+            Some(AutomationAction::LockIn {
+                action_id: 1,
+                champion_id: ChampionId(1)
+            })
+        );
+    }
+
+    #[test]
+    fn test_waits_for_turn() {
+        let mut automation = ChampSelectAutomation::new().default_picks(vec![ChampionId(1)]);
+
+        // The enemy team is picking
+        assert_eq!(
+            automation.update(&availability(&fixture())),
+            AutomationStep::default()
+        );
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod driver_tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use reqwest::Method;
+
+    use crate::champ_select_automation::ChampSelectAutomation;
+    use crate::endpoints::fixtures::load_fixture;
+    use crate::endpoints::ids::ChampionId;
+    use crate::testing::{FakeTransport, MockResponse};
+    use crate::websocket::LcuEventType;
+
+    const ACTION_PATH: &str = "/lol-champ-select/v1/session/actions/1";
+
+    async fn transport(action_response: MockResponse) -> FakeTransport {
+        let transport = FakeTransport::new();
+
+        transport.route(
+            Method::GET,
+            "/lol-champ-select/v1/session",
+            MockResponse::json(&load_fixture("champ_select/practice_tool.json")),
+        );
+        for (path, champion_ids) in [
+            ("pickable-champion-ids", vec![ChampionId(103)]),
+            ("bannable-champion-ids", vec![ChampionId(103)]),
+            ("disabled-champion-ids", vec![]),
+        ] {
+            transport.route(
+                Method::GET,
+                &format!("/lol-champ-select/v1/{}", path),
+                MockResponse::json(&champion_ids),
+            );
+        }
+        transport.route(Method::PATCH, ACTION_PATH, action_response);
+
+        transport
+    }
+
+    fn action_patches(transport: &FakeTransport) -> Vec<Option<String>> {
+        transport
+            .requests()
+            .into_iter()
+            .filter(|r| r.method == Method::PATCH && r.path == ACTION_PATH)
+            .map(|r| r.body)
+            .collect()
+    }
+
+    async fn wait_for_action_patches(transport: &FakeTransport, count: usize) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while action_patches(transport).len() < count {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("Action was not sent");
+    }
+
+    #[tokio::test]
+    async fn test_locks_in_current_session() {
+        let transport = transport(MockResponse::no_content()).await;
+        let driver = transport
+            .driver_builder("./test_data/lockfile")
+            .connect()
+            .await
+            .unwrap();
+
+        let handle = ChampSelectAutomation::new()
+            .default_picks(vec![ChampionId(103)])
+            .spawn(Arc::new(driver));
+
+        wait_for_action_patches(&transport, 1).await;
+        handle.abort();
+
+        assert_eq!(
+            action_patches(&transport)[0].as_deref(),
+            Some(r#"{"championId":103,"completed":true}"#)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retries_after_failed_action() {
+        let transport = transport(MockResponse::error(500, "Action failed")).await;
+        let driver = transport
+            .driver_builder("./test_data/lockfile")
+            .connect()
+            .await
+            .unwrap();
+
+        let handle = ChampSelectAutomation::new()
+            .default_picks(vec![ChampionId(103)])
+            .spawn(Arc::new(driver));
+
+        transport
+            .wait_for_subscription("OnJsonApiEvent_lol-champ-select_v1_session")
+            .await;
+        wait_for_action_patches(&transport, 1).await;
+
+        transport.route(Method::PATCH, ACTION_PATH, MockResponse::no_content());
+        transport.push_event(
+            "/lol-champ-select/v1/session",
+            LcuEventType::Update,
+            &load_fixture("champ_select/practice_tool.json"),
+        );
+
+        wait_for_action_patches(&transport, 2).await;
+
+        assert!(!handle.is_finished());
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_sends_nothing() {
+        let transport = transport(MockResponse::no_content()).await;
+        let driver = transport
+            .driver_builder("./test_data/lockfile")
+            .connect()
+            .await
+            .unwrap();
+
+        let handle = ChampSelectAutomation::new()
+            .default_picks(vec![ChampionId(103)])
+            .dry_run(true)
+            .spawn(Arc::new(driver));
+
+        transport
+            .wait_for_subscription("OnJsonApiEvent_lol-champ-select_v1_session")
+            .await;
+
+        // The engine stops once the websocket closes, after handling the session it read on start
+        transport.close_event_channels();
+
+        tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .expect("Engine did not stop")
+            .unwrap()
+            .unwrap();
+
+        assert!(action_patches(&transport).is_empty());
+    }
+}
@@ -0,0 +1,363 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::endpoints::champ_select::{
+    Action, ActionType, ChampSelectPhase, ChampSelectSession, TradeContractState,
+};
+use crate::endpoints::ids::{CellId, ChampionId};
+use crate::websocket::{LcuEvent, LcuEventType};
+use crate::Result;
+
+pub const CHAMP_SELECT_SESSION_URI: &str = "/lol-champ-select/v1/session";
+
+// A deadline moving later by more than this is a reset rather than clock drift between snapshots.
+const TIMER_RESET_TOLERANCE_MS: isize = 1000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChampSelectEvent {
+    PhaseChanged {
+        from: Option<ChampSelectPhase>,
+        to: ChampSelectPhase,
+    },
+    // The local player has a pick or ban to make
+    MyTurnStarted(Action),
+    AllyHovered {
+        cell_id: CellId,
+        champion_id: ChampionId,
+    },
+    AllyLocked {
+        cell_id: CellId,
+        champion_id: ChampionId,
+    },
+    BanCompleted {
+        cell_id: CellId,
+        champion_id: ChampionId,
+        is_ally: bool,
+    },
+    // Another player asked to trade champions with the local player
+    TradeOffered {
+        trade_id: isize,
+        cell_id: CellId,
+    },
+    TimerReset {
+        phase: ChampSelectPhase,
+        time_left: Duration,
+    },
+}
+
+/*
+    Turns successive champ select sessions into the events that happened between them. Sessions can come
+    from polling `get_champ_select_session` or from websocket events on `CHAMP_SELECT_SESSION_URI`. The
+    first session is compared against an empty one, so it reports everything that already happened.
+*/
+#[derive(Debug, Default)]
+pub struct ChampSelectTracker {
+    previous: Option<ChampSelectSession>,
+}
+
+impl ChampSelectTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // The last session that was passed in.
+    pub fn session(&self) -> Option<&ChampSelectSession> {
+        self.previous.as_ref()
+    }
+
+    // Forget the last session, e.g. once champ select has ended.
+    pub fn reset(&mut self) {
+        self.previous = None;
+    }
+
+    pub fn update(&mut self, session: ChampSelectSession) -> Vec<ChampSelectEvent> {
+        let events = diff(self.previous.as_ref(), &session);
+
+        self.previous = Some(session);
+
+        events
+    }
+
+    // Feed a websocket event on `CHAMP_SELECT_SESSION_URI`, a delete means champ select has ended.
+    pub fn update_from_event(&mut self, event: LcuEvent) -> Result<Vec<ChampSelectEvent>> {
+        if event.event_type == LcuEventType::Delete {
+            self.reset();
+
+            return Ok(Vec::new());
+        }
+
+        let session = serde_json::from_value(event.data)?;
+
+        Ok(self.update(session))
+    }
+}
+
+fn diff(
+    previous: Option<&ChampSelectSession>,
+    session: &ChampSelectSession,
+) -> Vec<ChampSelectEvent> {
+    let mut events = Vec::new();
+
+    let previous_phase = previous.map(|p| p.timer.phase);
+
+    if previous_phase != Some(session.timer.phase) {
+        events.push(ChampSelectEvent::PhaseChanged {
+            from: previous_phase,
+            to: session.timer.phase,
+        });
+    }
+
+    if let Some(previous) = previous {
+        if timer_was_reset(previous, session) {
+            events.push(ChampSelectEvent::TimerReset {
+                phase: session.timer.phase,
+                time_left: Duration::from_millis(
+                    session.timer.adjusted_time_left_in_phase.max(0) as u64
+                ),
+            });
+        }
+    }
+
+    let previous_actions: HashMap<isize, &Action> = previous
+        .into_iter()
+        .flat_map(|p| p.actions.iter().flatten().flatten())
+        .map(|a| (a.id, a))
+        .collect();
+
+    for action in session.actions.iter().flatten().flatten() {
+        let before = previous_actions.get(&action.id);
+        let newly_completed = action.completed && !before.is_some_and(|b| b.completed);
+        let is_ally = action.is_ally_action && action.actor_cell_id != session.local_player_cell_id;
+
+        match action.type_field {
+            ActionType::Ban if newly_completed => events.push(ChampSelectEvent::BanCompleted {
+                cell_id: action.actor_cell_id,
+                champion_id: action.champion_id,
+                is_ally: action.is_ally_action,
+            }),
+            ActionType::Pick if is_ally && action.champion_id != ChampionId::NONE => {
+                if newly_completed {
+                    events.push(ChampSelectEvent::AllyLocked {
+                        cell_id: action.actor_cell_id,
+                        champion_id: action.champion_id,
+                    });
+                } else if !action.completed
+                    && before.map(|b| b.champion_id) != Some(action.champion_id)
+                {
+                    events.push(ChampSelectEvent::AllyHovered {
+                        cell_id: action.actor_cell_id,
+                        champion_id: action.champion_id,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /*
+        Hovers made during planning, before any pick action has started. Afterwards the client mirrors
+        hovers into the pick intent as well, which the actions above already report.
+    */
+    if session.timer.phase == ChampSelectPhase::Planning {
+        for player in &session.my_team {
+            if player.cell_id == session.local_player_cell_id
+                || player.champion_pick_intent == ChampionId::NONE
+            {
+                continue;
+            }
+
+            let intent_before = previous
+                .and_then(|p| p.my_team.iter().find(|b| b.cell_id == player.cell_id))
+                .map(|b| b.champion_pick_intent);
+
+            if intent_before != Some(player.champion_pick_intent) {
+                events.push(ChampSelectEvent::AllyHovered {
+                    cell_id: player.cell_id,
+                    champion_id: player.champion_pick_intent,
+                });
+            }
+        }
+    }
+
+    for trade in &session.trades {
+        let was_received = previous
+            .and_then(|p| p.trades.iter().find(|b| b.id == trade.id))
+            .is_some_and(|b| b.state == TradeContractState::Received);
+
+        if trade.state == TradeContractState::Received && !was_received {
+            events.push(ChampSelectEvent::TradeOffered {
+                trade_id: trade.id,
+                cell_id: trade.cell_id,
+            });
+        }
+    }
+
+    if let Some(action) = session.current_action() {
+        let previous_action = previous.and_then(|p| p.current_action());
+
+        if previous_action.map(|a| a.id) != Some(action.id) {
+            events.push(ChampSelectEvent::MyTurnStarted(action.clone()));
+        }
+    }
+
+    events
+}
+
+fn timer_was_reset(previous: &ChampSelectSession, session: &ChampSelectSession) -> bool {
+    if session.timer.is_infinite {
+        return false;
+    }
+
+    let deadline = |s: &ChampSelectSession| {
+        s.timer.internal_now_in_epoch_ms + s.timer.adjusted_time_left_in_phase
+    };
+
+    deadline(session) > deadline(previous) + TIMER_RESET_TOLERANCE_MS
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::{json, Value};
+
+    use crate::champ_select_tracker::{ChampSelectEvent, ChampSelectTracker};
+    use crate::endpoints::champ_select::{ChampSelectPhase, ChampSelectSession};
+    use crate::endpoints::fixtures::{load_fixture, session_action};
+    use crate::endpoints::ids::{CellId, ChampionId};
+    use crate::websocket::{LcuEvent, LcuEventType};
+
+    fn ranked_draft() -> Value {
+        load_fixture("champ_select/ranked_draft.json")
+    }
+
+    fn session(value: &Value) -> ChampSelectSession {
+        serde_json::from_value(value.clone()).unwrap()
+    }
+
+    #[test]
+    fn test_first_session_reports_history() {
+        let mut tracker = ChampSelectTracker::new();
+        let events = tracker.update(session(&ranked_draft()));
+
+        assert_eq!(
+            events[0],
+            ChampSelectEvent::PhaseChanged {
+                from: None,
+                to: ChampSelectPhase::BanPick
+            }
+        );
+        assert_eq!(
+            events
+                .iter()
+                .filter(|e| matches!(e, ChampSelectEvent::BanCompleted { .. }))
+                .count(),
+            10
+        );
+        assert!(events.contains(&ChampSelectEvent::AllyLocked {
+            cell_id: CellId(0),
+            champion_id: ChampionId(266)
+        }));
+    }
+
+    #[test]
+    fn test_unchanged_session_has_no_events() {
+        let mut tracker = ChampSelectTracker::new();
+
+        tracker.update(session(&ranked_draft()));
+
+        assert!(tracker.update(session(&ranked_draft())).is_empty());
+    }
+
+    #[test]
+    fn test_turn_transitions() {
+        let mut tracker = ChampSelectTracker::new();
+        let mut value = ranked_draft();
+
+        tracker.update(session(&value));
+
+        // The enemy locks in and it becomes our turn, with our ally hovering alongside us
+        session_action(&mut value, 13)["completed"] = json!(true);
+        session_action(&mut value, 13)["isInProgress"] = json!(false);
+        session_action(&mut value, 13)["championId"] = json!(11);
+        session_action(&mut value, 14)["isInProgress"] = json!(true);
+        session_action(&mut value, 15)["isInProgress"] = json!(true);
+        session_action(&mut value, 15)["championId"] = json!(103);
+        value["timer"]["adjustedTimeLeftInPhase"] = json!(30000);
+        value["timer"]["internalNowInEpochMs"] =
+            json!(value["timer"]["internalNowInEpochMs"].as_i64().unwrap() + 5000);
+
+        let events = tracker.update(session(&value));
+
+        assert_eq!(
+            events,
+            vec![
+                ChampSelectEvent::TimerReset {
+                    phase: ChampSelectPhase::BanPick,
+                    time_left: Duration::from_secs(30)
+                },
+                ChampSelectEvent::AllyHovered {
+                    cell_id: CellId(2),
+                    champion_id: ChampionId(103)
+                },
+                ChampSelectEvent::MyTurnStarted(session(&value).current_action().unwrap().clone()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ban_pick_hover_is_reported_once() {
+        let mut tracker = ChampSelectTracker::new();
+        let mut value = ranked_draft();
+
+        tracker.update(session(&value));
+
+        // Outside of planning the client updates both the pick action and the pick intent
+        session_action(&mut value, 15)["championId"] = json!(22);
+        value["myTeam"][2]["championPickIntent"] = json!(22);
+
+        assert_eq!(
+            tracker.update(session(&value)),
+            vec![ChampSelectEvent::AllyHovered {
+                cell_id: CellId(2),
+                champion_id: ChampionId(22)
+            }]
+        );
+    }
+
+    #[test]
+    fn test_trade_offered() {
+        let mut tracker = ChampSelectTracker::new();
+        let mut value = ranked_draft();
+
+        tracker.update(session(&value));
+
+        value["trades"][1]["state"] = json!("RECEIVED");
+
+        assert_eq!(
+            tracker.update(session(&value)),
+            vec![ChampSelectEvent::TradeOffered {
+                trade_id: 2,
+                cell_id: CellId(2)
+            }]
+        );
+    }
+
+    #[test]
+    fn test_delete_event_resets() {
+        let mut tracker = ChampSelectTracker::new();
+
+        tracker.update(session(&ranked_draft()));
+
+        let events = tracker
+            .update_from_event(LcuEvent {
+                data: Value::Null,
+                event_type: LcuEventType::Delete,
+                uri: "/lol-champ-select/v1/session".to_owned(),
+            })
+            .unwrap();
+
+        assert!(events.is_empty());
+        assert!(tracker.session().is_none());
+    }
+}
@@ -0,0 +1,697 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use heck::{ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+
+pub use schema::{ApiSchema, EndpointDef, FieldDef, TypeDef, TypeRef};
+
+mod schema;
+
+// Generated from the OpenAPI test schema, so that the tests check the output compiles
+#[cfg(test)]
+#[allow(dead_code)]
+#[rustfmt::skip]
+#[path = "../../test_data/codegen/generated/mod.rs"]
+mod generated;
+
+const HEADER: &str =
+    "// Generated by lcu_codegen from the client's API schema. Do not edit by hand.\n";
+
+const RUST_KEYWORDS: [&str; 38] = [
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while",
+];
+
+/*
+    Generates Rust bindings from an `ApiSchema`. The output is a module directory containing
+    `types.rs` with a struct or enum for every schema type, and one module per plugin with an
+    endpoint enum in the same shape as the hand-written ones in `endpoints`.
+*/
+#[derive(Debug, Clone)]
+pub struct Codegen {
+    schema: ApiSchema,
+    crate_path: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedFile {
+    // Relative to the output directory
+    pub path: PathBuf,
+    pub contents: String,
+}
+
+impl Codegen {
+    pub fn new(schema: ApiSchema) -> Self {
+        Self {
+            schema,
+            crate_path: "lcu_driver".to_owned(),
+        }
+    }
+
+    // How generated code refers to this crate, `crate` when generating into the driver itself.
+    pub fn crate_path<S: Into<String>>(mut self, path: S) -> Self {
+        self.crate_path = path.into();
+        self
+    }
+
+    pub fn generate(&self) -> Vec<GeneratedFile> {
+        let mut plugins: BTreeMap<String, Vec<&EndpointDef>> = BTreeMap::new();
+
+        for endpoint in &self.schema.endpoints {
+            plugins
+                .entry(plugin_name(&endpoint.path).to_owned())
+                .or_default()
+                .push(endpoint);
+        }
+
+        let mut module = String::from(HEADER);
+        module.push('\n');
+
+        for plugin in plugins.keys() {
+            writeln!(module, "pub mod {};", module_name(plugin)).unwrap();
+        }
+
+        module.push_str("pub mod types;\n");
+
+        let mut files = vec![
+            GeneratedFile {
+                path: PathBuf::from("mod.rs"),
+                contents: module,
+            },
+            GeneratedFile {
+                path: PathBuf::from("types.rs"),
+                contents: self.generate_types(),
+            },
+        ];
+
+        for (plugin, endpoints) in &plugins {
+            files.push(GeneratedFile {
+                path: PathBuf::from(format!("{}.rs", module_name(plugin))),
+                contents: self.generate_plugin(plugin, endpoints),
+            });
+        }
+
+        files
+    }
+
+    fn generate_types(&self) -> String {
+        let mut out = String::from(HEADER);
+        out.push('\n');
+
+        let uses_map = self.schema.types.iter().any(|ty| match ty {
+            TypeDef::Struct { fields, .. } => fields.iter().any(|f| f.ty.contains_map()),
+            TypeDef::Enum { .. } => false,
+        });
+
+        if uses_map {
+            out.push_str("use std::collections::HashMap;\n\n");
+        }
+
+        out.push_str("use serde::{Deserialize, Serialize};\n");
+
+        for ty in &self.schema.types {
+            out.push('\n');
+
+            match ty {
+                TypeDef::Struct { name, fields } => write_struct(&mut out, name, fields),
+                TypeDef::Enum { name, values } => write_enum(&mut out, name, values),
+            }
+        }
+
+        out
+    }
+
+    fn generate_plugin(&self, plugin: &str, endpoints: &[&EndpointDef]) -> String {
+        let enum_name = format!("{}Endpoint", type_name(plugin));
+        let url_const = format!("{}_URL", plugin.to_shouty_snake_case());
+        let prefix = plugin_prefix(plugin);
+
+        let variants: Vec<Variant> = unique_names(endpoints.iter().map(|e| type_name(&e.name)))
+            .into_iter()
+            .zip(endpoints)
+            .map(|(name, endpoint)| Variant::new(name, endpoint))
+            .collect();
+
+        let borrows = variants.iter().any(|v| v.fields.iter().any(|f| f.borrowed));
+        let lifetime = if borrows { "<'a>" } else { "" };
+
+        let fields = || variants.iter().flat_map(|v| &v.fields);
+
+        let imports: BTreeSet<String> = fields()
+            .flat_map(|f| f.ty.named_types())
+            .map(type_name)
+            .collect();
+
+        let mut out = String::from(HEADER);
+        out.push('\n');
+
+        if fields().any(|f| f.ty.contains_map()) {
+            out.push_str("use std::collections::HashMap;\n\n");
+        }
+
+        out.push_str("use reqwest::Method;\n\n");
+
+        if fields().any(|f| matches!(f.kind, FieldKind::Query { .. })) {
+            writeln!(
+                out,
+                "use {}::endpoints::{{url_with_query, EndpointInfo}};",
+                self.crate_path
+            )
+            .unwrap();
+        } else {
+            writeln!(out, "use {}::endpoints::EndpointInfo;", self.crate_path).unwrap();
+        }
+
+        match imports.len() {
+            0 => {}
+            1 => writeln!(out, "use super::types::{};", imports.iter().next().unwrap()).unwrap(),
+            _ => writeln!(
+                out,
+                "use super::types::{{{}}};",
+                imports.into_iter().collect::<Vec<_>>().join(", ")
+            )
+            .unwrap(),
+        }
+
+        if !prefix.is_empty() {
+            writeln!(out, "\nconst {}: &str = \"{}\";", url_const, prefix).unwrap();
+        }
+
+        writeln!(
+            out,
+            "\n#[allow(unused)]\npub enum {}{} {{",
+            enum_name, lifetime
+        )
+        .unwrap();
+
+        for variant in &variants {
+            let endpoint = variant.endpoint;
+
+            match &endpoint.response {
+                Some(response) => writeln!(
+                    out,
+                    "    // {} {} -> {}",
+                    endpoint.method,
+                    endpoint.path,
+                    response.rust_type()
+                ),
+                None => writeln!(out, "    // {} {}", endpoint.method, endpoint.path),
+            }
+            .unwrap();
+
+            if variant.fields.is_empty() {
+                writeln!(out, "    {},", variant.name).unwrap();
+            } else {
+                let fields: Vec<String> = variant.fields.iter().map(|f| f.field_type()).collect();
+                writeln!(out, "    {}({}),", variant.name, fields.join(", ")).unwrap();
+            }
+        }
+
+        out.push_str("}\n\n");
+
+        writeln!(out, "impl{} {}{} {{", lifetime, enum_name, lifetime).unwrap();
+        out.push_str("    pub fn info(&self) -> EndpointInfo {\n        match self {\n");
+
+        for variant in &variants {
+            let endpoint = variant.endpoint;
+
+            let pattern = if variant.fields.is_empty() {
+                format!("{}::{}", enum_name, variant.name)
+            } else {
+                let bindings: Vec<&str> =
+                    variant.fields.iter().map(|f| f.binding.as_str()).collect();
+                format!("{}::{}({})", enum_name, variant.name, bindings.join(", "))
+            };
+
+            let body = match variant
+                .fields
+                .iter()
+                .find(|f| matches!(f.kind, FieldKind::Body))
+            {
+                Some(field) => format!("serde_json::to_string({}).ok()", field.binding),
+                None => "None".to_owned(),
+            };
+
+            writeln!(out, "            {} => EndpointInfo {{", pattern).unwrap();
+            writeln!(
+                out,
+                "                url: {},",
+                variant.url(&url_const, &prefix)
+            )
+            .unwrap();
+            writeln!(out, "                method: Method::{},", endpoint.method).unwrap();
+            out.push_str("                headers: None,\n");
+            writeln!(out, "                body: {},", body).unwrap();
+            out.push_str("            },\n");
+        }
+
+        out.push_str("        }\n    }\n}\n");
+
+        out
+    }
+}
+
+struct Variant<'e> {
+    name: String,
+    endpoint: &'e EndpointDef,
+    fields: Vec<VariantField>,
+}
+
+struct VariantField {
+    binding: String,
+    ty: TypeRef,
+    borrowed: bool,
+    kind: FieldKind,
+}
+
+enum FieldKind {
+    Path,
+    // The parameter is left out of the URL when an optional value is `None`
+    Query { name: String, optional: bool },
+    Body,
+}
+
+impl<'e> Variant<'e> {
+    fn new(name: String, endpoint: &'e EndpointDef) -> Self {
+        let path_params = endpoint.path_params.iter().map(|param| {
+            VariantField::new(field_name(&param.name), param.ty.clone(), FieldKind::Path)
+        });
+
+        let query_params = endpoint.query_params.iter().map(|param| {
+            let kind = FieldKind::Query {
+                name: param.name.clone(),
+                optional: param.optional,
+            };
+
+            VariantField::new(field_name(&param.name), param.ty.clone(), kind)
+        });
+
+        let body = endpoint
+            .body
+            .iter()
+            .map(|body| VariantField::new("body".to_owned(), body.clone(), FieldKind::Body));
+
+        let mut fields: Vec<VariantField> = path_params.chain(query_params).chain(body).collect();
+
+        let bindings = unique_names(fields.iter().map(|f| f.binding.clone()));
+
+        for (field, binding) in fields.iter_mut().zip(bindings) {
+            field.binding = binding;
+        }
+
+        Self {
+            name,
+            endpoint,
+            fields,
+        }
+    }
+
+    /*
+        A `format!` call for the URL, with path parameters in the order they appear in the path. Query
+        parameters are appended by `url_with_query`.
+    */
+    fn url(&self, url_const: &str, prefix: &str) -> String {
+        let path = &self.endpoint.path[prefix.len()..];
+
+        let mut template = String::new();
+        let mut rest = path;
+
+        while let Some((before, after)) = rest.split_once('{') {
+            template.push_str(before);
+            template.push_str("{}");
+            rest = after.split_once('}').map_or("", |(_, after)| after);
+        }
+
+        template.push_str(rest);
+
+        let mut args: Vec<&str> = Vec::new();
+
+        if !prefix.is_empty() {
+            template.insert_str(0, "{}");
+            args.push(url_const);
+        }
+
+        args.extend(
+            self.fields
+                .iter()
+                .filter(|f| matches!(f.kind, FieldKind::Path))
+                .map(|f| f.binding.as_str()),
+        );
+
+        let url = match args.as_slice() {
+            [] => format!("\"{}\".to_owned()", template),
+            [prefix_const] if template == "{}" => format!("{}.to_owned()", prefix_const),
+            args => format!("format!(\"{}\", {})", template, args.join(", ")),
+        };
+
+        let query: Vec<String> = self
+            .fields
+            .iter()
+            .filter_map(|f| match &f.kind {
+                FieldKind::Query { name, optional } => Some(format!(
+                    "(\"{}\", {})",
+                    name.escape_default(),
+                    f.query_value(*optional)
+                )),
+                _ => None,
+            })
+            .collect();
+
+        if query.is_empty() {
+            url
+        } else {
+            format!("url_with_query({}, &[{}])", url, query.join(", "))
+        }
+    }
+}
+
+impl VariantField {
+    fn new(binding: String, ty: TypeRef, kind: FieldKind) -> Self {
+        // Copy types are held by value, everything else is borrowed from the caller
+        let borrowed = !ty.is_copy();
+
+        Self {
+            binding,
+            ty,
+            borrowed,
+            kind,
+        }
+    }
+
+    fn field_type(&self) -> String {
+        let ty = match (&self.ty, self.borrowed) {
+            (TypeRef::Primitive("String"), _) => "&'a str".to_owned(),
+            (ty, true) => format!("&'a {}", ty.rust_type()),
+            (ty, false) => ty.rust_type(),
+        };
+
+        match self.kind {
+            FieldKind::Query { optional: true, .. } => format!("Option<{}>", ty),
+            _ => ty,
+        }
+    }
+
+    // An `Option<String>` expression for the value of a query parameter.
+    fn query_value(&self, optional: bool) -> String {
+        let display = self.ty.is_copy() || self.ty == TypeRef::Primitive("String");
+
+        match (optional, display) {
+            (false, true) => format!("Some({}.to_string())", self.binding),
+            (false, false) => format!("serde_json::to_string({}).ok()", self.binding),
+            (true, true) => format!("{}.map(|value| value.to_string())", self.binding),
+            (true, false) => format!(
+                "{}.and_then(|value| serde_json::to_string(value).ok())",
+                self.binding
+            ),
+        }
+    }
+}
+
+impl TypeRef {
+    pub fn rust_type(&self) -> String {
+        match self {
+            TypeRef::Primitive(p) => (*p).to_owned(),
+            TypeRef::Vec(inner) => format!("Vec<{}>", inner.rust_type()),
+            TypeRef::Map(inner) => format!("HashMap<String, {}>", inner.rust_type()),
+            TypeRef::Named(name) => type_name(name),
+        }
+    }
+
+    fn is_copy(&self) -> bool {
+        matches!(self, TypeRef::Primitive(p) if *p != "String" && *p != "serde_json::Value")
+    }
+
+    fn contains_map(&self) -> bool {
+        match self {
+            TypeRef::Map(_) => true,
+            TypeRef::Vec(inner) => inner.contains_map(),
+            _ => false,
+        }
+    }
+
+    fn named_types(&self) -> Vec<&str> {
+        match self {
+            TypeRef::Named(name) => vec![name.as_str()],
+            TypeRef::Vec(inner) | TypeRef::Map(inner) => inner.named_types(),
+            TypeRef::Primitive(_) => Vec::new(),
+        }
+    }
+}
+
+fn write_struct(out: &mut String, name: &str, fields: &[FieldDef]) {
+    out.push_str("#[derive(Debug, Clone, Serialize, Deserialize)]\n");
+    out.push_str("#[serde(rename_all = \"camelCase\")]\n");
+    writeln!(out, "pub struct {} {{", type_name(name)).unwrap();
+
+    let names = unique_names(fields.iter().map(|f| field_name(&f.name)));
+
+    for (field, rust_name) in fields.iter().zip(names) {
+        if rust_name.trim_start_matches("r#").to_lower_camel_case() != field.name {
+            writeln!(
+                out,
+                "    #[serde(rename = \"{}\")]",
+                field.name.escape_default()
+            )
+            .unwrap();
+        }
+
+        let ty = field.ty.rust_type();
+
+        if field.optional {
+            writeln!(out, "    pub {}: Option<{}>,", rust_name, ty).unwrap();
+        } else {
+            writeln!(out, "    pub {}: {},", rust_name, ty).unwrap();
+        }
+    }
+
+    out.push_str("}\n");
+}
+
+// Unknown values deserialize to an `Unknown` variant so that new values do not break the model.
+fn write_enum(out: &mut String, name: &str, values: &[String]) {
+    out.push_str("#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]\n");
+    writeln!(out, "pub enum {} {{", type_name(name)).unwrap();
+
+    let variants = unique_names(values.iter().map(|v| type_name(v)));
+
+    for (value, variant) in values.iter().zip(&variants) {
+        if value != variant {
+            writeln!(out, "    #[serde(rename = \"{}\")]", value.escape_default()).unwrap();
+        }
+
+        writeln!(out, "    {},", variant).unwrap();
+    }
+
+    if !variants.iter().any(|v| v == "Unknown") {
+        out.push_str("    #[serde(other)]\n    Unknown,\n");
+    }
+
+    out.push_str("}\n");
+}
+
+// Endpoints are grouped by the first segment of their path, e.g. "lol-summoner".
+fn plugin_name(path: &str) -> &str {
+    match path.trim_start_matches('/').split('/').next() {
+        Some(segment) if !segment.is_empty() && !segment.contains('{') => segment,
+        _ => "builtin",
+    }
+}
+
+fn plugin_prefix(plugin: &str) -> String {
+    match plugin {
+        "builtin" => String::new(),
+        _ => format!("/{}", plugin),
+    }
+}
+
+fn module_name(plugin: &str) -> String {
+    identifier(plugin.to_snake_case())
+}
+
+fn type_name(name: &str) -> String {
+    let name = name.to_upper_camel_case();
+
+    match name.chars().next() {
+        Some(c) if c.is_ascii_digit() => format!("Value{}", name),
+        Some(_) => name,
+        None => "Empty".to_owned(),
+    }
+}
+
+// `type` becomes `type_field` like in the hand-written models, other keywords are escaped.
+fn field_name(name: &str) -> String {
+    match name.to_snake_case().as_str() {
+        "type" => "type_field".to_owned(),
+        name => identifier(name.to_owned()),
+    }
+}
+
+fn identifier(name: String) -> String {
+    match name.chars().next() {
+        None => "field".to_owned(),
+        Some(c) if c.is_ascii_digit() => format!("field_{}", name),
+        Some(_) if RUST_KEYWORDS.contains(&name.as_str()) => format!("r#{}", name),
+        Some(_) => name,
+    }
+}
+
+// Append a counter to names that have been used before, keeping the order.
+fn unique_names<I: IntoIterator<Item = String>>(names: I) -> Vec<String> {
+    let mut seen = BTreeSet::new();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let mut unique = name.clone();
+            let mut counter = 1;
+
+            while !seen.insert(unique.clone()) {
+                counter += 1;
+                unique = format!("{}{}", name, counter);
+            }
+
+            unique
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::codegen::generated::lol_summoner::LolSummonerEndpoint;
+    use crate::codegen::{ApiSchema, Codegen, TypeDef, TypeRef};
+
+    fn generated(schema: &ApiSchema, file: &str) -> String {
+        Codegen::new(schema.clone())
+            .crate_path("crate")
+            .generate()
+            .into_iter()
+            .find(|f| f.path.to_str() == Some(file))
+            .map(|f| f.contents)
+            .expect("File was not generated")
+    }
+
+    #[test]
+    fn test_openapi_and_help_agree() {
+        let openapi =
+            ApiSchema::parse(&std::fs::read_to_string("./test_data/codegen/openapi.json").unwrap())
+                .expect("Failed to parse OpenAPI schema");
+
+        let help =
+            ApiSchema::parse(&std::fs::read_to_string("./test_data/codegen/help.json").unwrap())
+                .expect("Failed to parse help schema");
+
+        assert_eq!(openapi.endpoints, help.endpoints);
+
+        for ty in &help.types {
+            assert!(openapi.types.contains(ty), "{} differs", ty.name());
+        }
+    }
+
+    #[test]
+    fn test_generate_types() {
+        let schema =
+            ApiSchema::parse(&std::fs::read_to_string("./test_data/codegen/openapi.json").unwrap())
+                .unwrap();
+
+        let types = generated(&schema, "types.rs");
+
+        assert!(types.contains("pub struct LolSummonerSummoner {"));
+        assert!(types.contains("    pub account_id: u64,\n"));
+        assert!(types.contains("    #[serde(rename = \"isRGM\")]\n    pub is_rgm: bool,\n"));
+        assert!(types.contains("    pub type_field: String,\n"));
+        assert!(types.contains("    #[serde(rename = \"IN_PROGRESS\")]\n    InProgress,\n"));
+        assert!(types.contains("    #[serde(other)]\n    Unknown,\n"));
+    }
+
+    #[test]
+    fn test_generate_endpoints() {
+        let schema =
+            ApiSchema::parse(&std::fs::read_to_string("./test_data/codegen/openapi.json").unwrap())
+                .unwrap();
+
+        let module = generated(&schema, "mod.rs");
+
+        assert!(module.contains("pub mod lol_summoner;\n"));
+        assert!(module.contains("pub mod builtin;\n"));
+
+        let summoner = generated(&schema, "lol_summoner.rs");
+
+        assert!(summoner.contains("const LOL_SUMMONER_URL: &str = \"/lol-summoner\";"));
+        assert!(summoner.contains("pub enum LolSummonerEndpoint<'a> {"));
+        assert!(summoner.contains("    GetLolSummonerV1SummonersById(u64),\n"));
+        assert!(summoner
+            .contains("    PutLolSummonerV1CurrentSummonerIcon(&'a LolSummonerSummonerIcon),\n"));
+        assert!(summoner.contains("url: format!(\"{}/v1/summoners/{}\", LOL_SUMMONER_URL, id),"));
+        assert!(summoner.contains("body: serde_json::to_string(body).ok(),"));
+
+        // Imports follow the types that are used
+        assert!(generated(&schema, "lol_settings.rs").contains("use std::collections::HashMap;\n"));
+        assert!(!summoner.contains("HashMap"));
+
+        let builtin = generated(&schema, "builtin.rs");
+
+        assert!(builtin.contains("pub enum BuiltinEndpoint<'a> {"));
+        assert!(builtin.contains("url: format!(\"/{}/assets/{}\", plugin, path),"));
+    }
+
+    #[test]
+    fn test_generated_fixture_is_current() {
+        let schema =
+            ApiSchema::parse(&std::fs::read_to_string("./test_data/codegen/openapi.json").unwrap())
+                .unwrap();
+
+        for file in Codegen::new(schema).crate_path("crate").generate() {
+            let path = std::path::Path::new("./test_data/codegen/generated").join(&file.path);
+
+            assert_eq!(
+                std::fs::read_to_string(&path).ok().as_deref(),
+                Some(file.contents.as_str()),
+                "{} is out of date, regenerate it with lcu_codegen without rustfmt",
+                path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn test_generated_query_params() {
+        let endpoint = LolSummonerEndpoint::GetLolSummonerV1Summoners("Mock Summoner", None);
+
+        assert_eq!(
+            endpoint.info().url,
+            "/lol-summoner/v1/summoners?name=Mock+Summoner"
+        );
+
+        let endpoint = LolSummonerEndpoint::GetLolSummonerV1Summoners("Mock", Some(true));
+
+        assert_eq!(
+            endpoint.info().url,
+            "/lol-summoner/v1/summoners?name=Mock&includeIcon=true"
+        );
+    }
+
+    #[test]
+    fn test_help_body_argument() {
+        let help =
+            ApiSchema::parse(&std::fs::read_to_string("./test_data/codegen/help.json").unwrap())
+                .unwrap();
+
+        let icon = help
+            .endpoints
+            .iter()
+            .find(|e| e.name == "PutLolSummonerV1CurrentSummonerIcon")
+            .unwrap();
+
+        assert_eq!(
+            icon.body,
+            Some(TypeRef::Named("LolSummonerSummonerIcon".to_owned()))
+        );
+
+        assert!(matches!(
+            help.types.iter().find(|t| t.name() == "LolGameflowGameflowPhase"),
+            Some(TypeDef::Enum { values, .. }) if values.len() == 3
+        ));
+    }
+}
@@ -0,0 +1,451 @@
+use std::collections::BTreeSet;
+
+use reqwest::Method;
+use serde_json::Value;
+
+use crate::errors::LcuDriverError;
+use crate::Result;
+
+const HTTP_METHODS: [&str; 6] = ["get", "post", "put", "patch", "delete", "head"];
+
+/*
+    The parts of the client's API description the generator needs, read from either the OpenAPI
+    dump or the `/help?format=Full` output. Both describe the same API, so they are reduced to
+    the same shape here.
+*/
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ApiSchema {
+    pub types: Vec<TypeDef>,
+    pub endpoints: Vec<EndpointDef>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeDef {
+    Struct { name: String, fields: Vec<FieldDef> },
+    Enum { name: String, values: Vec<String> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDef {
+    pub name: String,
+    pub ty: TypeRef,
+    pub optional: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeRef {
+    // A Rust primitive, `String` or `serde_json::Value`
+    Primitive(&'static str),
+    Vec(Box<TypeRef>),
+    Map(Box<TypeRef>),
+    // A type defined in the schema, by its schema name
+    Named(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndpointDef {
+    pub name: String,
+    pub method: Method,
+    // The URL with path parameters in braces, e.g. "/lol-summoner/v1/summoners/{id}"
+    pub path: String,
+    pub path_params: Vec<FieldDef>,
+    pub query_params: Vec<FieldDef>,
+    pub body: Option<TypeRef>,
+    pub response: Option<TypeRef>,
+}
+
+impl TypeDef {
+    pub fn name(&self) -> &str {
+        match self {
+            TypeDef::Struct { name, .. } | TypeDef::Enum { name, .. } => name,
+        }
+    }
+}
+
+impl ApiSchema {
+    // Parse a schema file, detecting whether it is an OpenAPI dump or `/help` output.
+    pub fn parse(contents: &str) -> Result<Self> {
+        let schema: Value = serde_json::from_str(contents)?;
+
+        if schema.get("paths").is_some() {
+            Self::from_openapi(&schema)
+        } else if schema.get("functions").is_some() {
+            Self::from_help(&schema)
+        } else {
+            Err(invalid(
+                "expected an OpenAPI document or /help?format=Full output",
+            ))
+        }
+    }
+
+    /*
+        Read an OpenAPI 3 or swagger 2 document. The client does not mark any properties as required,
+        so properties are only optional when a schema has a `required` list that leaves them out.
+    */
+    pub fn from_openapi(document: &Value) -> Result<Self> {
+        let schemas = document
+            .pointer("/components/schemas")
+            .or_else(|| document.get("definitions"))
+            .and_then(Value::as_object);
+
+        let types = schemas
+            .into_iter()
+            .flatten()
+            .map(|(name, schema)| openapi_type_def(name, schema))
+            .collect();
+
+        let paths = document
+            .get("paths")
+            .and_then(Value::as_object)
+            .ok_or_else(|| invalid("missing paths"))?;
+
+        let mut endpoints = Vec::new();
+
+        for (path, operations) in paths {
+            for method in HTTP_METHODS {
+                if let Some(operation) = operations.get(method) {
+                    endpoints.push(openapi_endpoint(path, method, operation)?);
+                }
+            }
+        }
+
+        Ok(Self { types, endpoints })
+    }
+
+    // Read the output of `/help?format=Full`. Functions without a URL are not HTTP endpoints.
+    pub fn from_help(help: &Value) -> Result<Self> {
+        let types = help
+            .get("types")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(help_type_def)
+            .collect();
+
+        let mut endpoints = Vec::new();
+
+        for function in help
+            .get("functions")
+            .and_then(Value::as_array)
+            .ok_or_else(|| invalid("missing functions"))?
+        {
+            let (path, method) = match (
+                function.get("url").and_then(Value::as_str),
+                function.get("http_method").and_then(Value::as_str),
+            ) {
+                (Some(path), Some(method)) if !path.is_empty() => (path, method),
+                _ => continue,
+            };
+
+            endpoints.push(help_endpoint(path, method, function)?);
+        }
+
+        Ok(Self { types, endpoints })
+    }
+}
+
+fn invalid(message: &str) -> LcuDriverError {
+    LcuDriverError::new(format!("Invalid API schema - {}", message))
+}
+
+fn parse_method(method: &str) -> Result<Method> {
+    Method::from_bytes(method.to_uppercase().as_bytes())
+        .map_err(|_| invalid(&format!("unknown HTTP method {}", method)))
+}
+
+// The names of the path parameters in a URL, in order.
+pub(crate) fn url_params(path: &str) -> Vec<String> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}'))
+        .map(|(name, _)| name.to_owned())
+        .collect()
+}
+
+fn path_params<F: Fn(&str) -> Option<TypeRef>>(path: &str, lookup: F) -> Vec<FieldDef> {
+    url_params(path)
+        .into_iter()
+        .map(|name| FieldDef {
+            ty: lookup(&name).unwrap_or(TypeRef::Primitive("String")),
+            name,
+            optional: false,
+        })
+        .collect()
+}
+
+fn openapi_type_def(name: &str, schema: &Value) -> TypeDef {
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        return TypeDef::Enum {
+            name: name.to_owned(),
+            values: values
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_owned)
+                .collect(),
+        };
+    }
+
+    let required = schema.get("required").and_then(Value::as_array).map(|r| {
+        r.iter()
+            .filter_map(Value::as_str)
+            .collect::<BTreeSet<&str>>()
+    });
+
+    let fields = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .map(|(field, property)| FieldDef {
+            name: field.to_owned(),
+            ty: openapi_type(property),
+            optional: required
+                .as_ref()
+                .is_some_and(|required| !required.contains(field.as_str())),
+        })
+        .collect();
+
+    TypeDef::Struct {
+        name: name.to_owned(),
+        fields,
+    }
+}
+
+fn openapi_type(schema: &Value) -> TypeRef {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        return TypeRef::Named(reference.rsplit('/').next().unwrap_or(reference).to_owned());
+    }
+
+    // The client wraps some references in a single element allOf
+    if let Some([inner]) = schema
+        .get("allOf")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+    {
+        return openapi_type(inner);
+    }
+
+    let format = schema.get("format").and_then(Value::as_str).unwrap_or("");
+
+    match schema.get("type").and_then(Value::as_str).unwrap_or("") {
+        "boolean" => TypeRef::Primitive("bool"),
+        "integer" => TypeRef::Primitive(integer_type(format)),
+        "number" if format == "float" => TypeRef::Primitive("f32"),
+        "number" => TypeRef::Primitive("f64"),
+        "string" => TypeRef::Primitive("String"),
+        "array" => TypeRef::Vec(Box::new(
+            schema
+                .get("items")
+                .map(openapi_type)
+                .unwrap_or(TypeRef::Primitive("serde_json::Value")),
+        )),
+        "object" => match schema.get("additionalProperties") {
+            Some(values) if values.is_object() => TypeRef::Map(Box::new(openapi_type(values))),
+            _ => TypeRef::Primitive("serde_json::Value"),
+        },
+        _ => TypeRef::Primitive("serde_json::Value"),
+    }
+}
+
+fn integer_type(format: &str) -> &'static str {
+    match format {
+        "int8" | "int16" | "int32" => "i32",
+        "uint8" | "uint16" | "uint32" => "u32",
+        "uint64" => "u64",
+        _ => "i64",
+    }
+}
+
+fn openapi_endpoint(path: &str, method: &str, operation: &Value) -> Result<EndpointDef> {
+    let parameters = operation
+        .get("parameters")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let parameter = |location: &str, name: Option<&str>| {
+        parameters.iter().find(|p| {
+            p.get("in").and_then(Value::as_str) == Some(location)
+                && name.is_none_or(|name| p.get("name").and_then(Value::as_str) == Some(name))
+        })
+    };
+
+    let path_params = path_params(path, |name| {
+        // OpenAPI 3 nests the type in a schema, swagger 2 puts it on the parameter itself
+        parameter("path", Some(name)).map(|p| openapi_type(p.get("schema").unwrap_or(p)))
+    });
+
+    let query_params = parameters
+        .iter()
+        .filter(|p| p.get("in").and_then(Value::as_str) == Some("query"))
+        .filter_map(|p| {
+            Some(FieldDef {
+                name: p.get("name").and_then(Value::as_str)?.to_owned(),
+                ty: openapi_type(p.get("schema").unwrap_or(p)),
+                optional: !p.get("required").and_then(Value::as_bool).unwrap_or(false),
+            })
+        })
+        .collect();
+
+    let body = operation
+        .pointer("/requestBody/content/application~1json/schema")
+        .or_else(|| parameter("body", None).and_then(|p| p.get("schema")))
+        .map(openapi_type);
+
+    let response = operation
+        .get("responses")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .filter(|(status, _)| status.starts_with('2'))
+        .find_map(|(_, response)| {
+            response
+                .pointer("/content/application~1json/schema")
+                .or_else(|| response.get("schema"))
+        })
+        .map(openapi_type);
+
+    Ok(EndpointDef {
+        name: operation
+            .get("operationId")
+            .and_then(Value::as_str)
+            .map(str::to_owned)
+            .unwrap_or_else(|| format!("{} {}", method, path)),
+        method: parse_method(method)?,
+        path: path.to_owned(),
+        path_params,
+        query_params,
+        body,
+        response,
+    })
+}
+
+fn help_type_def(definition: &Value) -> Option<TypeDef> {
+    let name = definition.get("name").and_then(Value::as_str)?.to_owned();
+
+    let values = definition
+        .get("values")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    if !values.is_empty() {
+        return Some(TypeDef::Enum {
+            name,
+            values: values
+                .iter()
+                .filter_map(|v| v.get("name").and_then(Value::as_str))
+                .map(str::to_owned)
+                .collect(),
+        });
+    }
+
+    let fields = definition
+        .get("fields")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|field| {
+            Some(FieldDef {
+                name: field.get("name").and_then(Value::as_str)?.to_owned(),
+                ty: help_type(field.get("type")?),
+                optional: field
+                    .get("optional")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false),
+            })
+        })
+        .collect();
+
+    Some(TypeDef::Struct { name, fields })
+}
+
+// `/help` types are objects of the form `{ "type": "vector", "elementType": "string" }`.
+fn help_type(ty: &Value) -> TypeRef {
+    let name = ty.get("type").and_then(Value::as_str).unwrap_or("");
+    let element = ty.get("elementType").and_then(Value::as_str).unwrap_or("");
+
+    match name {
+        "vector" => TypeRef::Vec(Box::new(help_type_name(element))),
+        "map" => TypeRef::Map(Box::new(help_type_name(element))),
+        _ => help_type_name(name),
+    }
+}
+
+fn help_type_name(name: &str) -> TypeRef {
+    match name {
+        "bool" => TypeRef::Primitive("bool"),
+        "int8" | "int16" | "int32" | "uint8" | "uint16" | "uint32" | "int64" | "uint64" => {
+            TypeRef::Primitive(integer_type(name))
+        }
+        "float" => TypeRef::Primitive("f32"),
+        "double" => TypeRef::Primitive("f64"),
+        "string" => TypeRef::Primitive("String"),
+        "" | "object" => TypeRef::Primitive("serde_json::Value"),
+        name => TypeRef::Named(name.to_owned()),
+    }
+}
+
+/*
+    `/help` does not say where non-path arguments go. Primitive arguments are query parameters and
+    the first structured argument is the request body.
+*/
+fn help_endpoint(path: &str, method: &str, function: &Value) -> Result<EndpointDef> {
+    let arguments: Vec<FieldDef> = function
+        .get("arguments")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|argument| {
+            Some(FieldDef {
+                name: argument.get("name").and_then(Value::as_str)?.to_owned(),
+                ty: help_type(argument.get("type")?),
+                optional: argument
+                    .get("optional")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false),
+            })
+        })
+        .collect();
+
+    let params = url_params(path);
+
+    let path_params = path_params(path, |name| {
+        arguments
+            .iter()
+            .find(|argument| argument.name == name)
+            .map(|argument| argument.ty.clone())
+    });
+
+    let (query_params, structured): (Vec<FieldDef>, Vec<FieldDef>) = arguments
+        .into_iter()
+        .filter(|argument| !params.contains(&argument.name))
+        .partition(
+            |argument| matches!(argument.ty, TypeRef::Primitive(p) if p != "serde_json::Value"),
+        );
+
+    let body = structured.into_iter().next().map(|argument| argument.ty);
+
+    let response = function
+        .get("returns")
+        .filter(|r| {
+            r.get("type")
+                .and_then(Value::as_str)
+                .is_some_and(|t| !t.is_empty())
+        })
+        .map(help_type);
+
+    Ok(EndpointDef {
+        name: function
+            .get("name")
+            .and_then(Value::as_str)
+            .map(str::to_owned)
+            .unwrap_or_else(|| format!("{} {}", method, path)),
+        method: parse_method(method)?,
+        path: path.to_owned(),
+        path_params,
+        query_params,
+        body,
+        response,
+    })
+}
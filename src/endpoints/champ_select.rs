@@ -1,14 +1,45 @@
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
 
+use crate::endpoints::ids::{CellId, ChampionId, GameId, SkinId, SpellId, SummonerId};
+use crate::endpoints::lobby::LobbyPosition;
 use crate::endpoints::EndpointInfo;
 
 const CHAMP_SELECT_URL: &str = "/lol-champ-select/v1";
 
+// Summoner spell IDs, as used by `MySelection` and the game data.
+pub mod summoner_spells {
+    use crate::endpoints::ids::SpellId;
+
+    pub const CLEANSE: SpellId = SpellId(1);
+    pub const EXHAUST: SpellId = SpellId(3);
+    pub const FLASH: SpellId = SpellId(4);
+    pub const GHOST: SpellId = SpellId(6);
+    pub const HEAL: SpellId = SpellId(7);
+    pub const SMITE: SpellId = SpellId(11);
+    pub const TELEPORT: SpellId = SpellId(12);
+    pub const CLARITY: SpellId = SpellId(13);
+    pub const IGNITE: SpellId = SpellId(14);
+    pub const BARRIER: SpellId = SpellId(21);
+    // Mark/Dash in ARAM
+    pub const MARK: SpellId = SpellId(32);
+}
+
 #[allow(unused)]
 pub enum ChampSelectEndpoint<'a> {
     Session,
     SessionMySelection(&'a str),
+    SessionAction(isize, &'a str),
+    CompleteSessionAction(isize),
+    Trade(isize, ContractAction),
+    PickOrderSwap(isize, ContractAction),
+    PositionSwap(isize, ContractAction),
+    BenchSwap(ChampionId),
+    Reroll,
+    PickableChampionIds,
+    BannableChampionIds,
+    DisabledChampionIds,
+    SkinCarouselSkins,
 }
 
 impl<'a> ChampSelectEndpoint<'a> {
@@ -26,6 +57,110 @@ impl<'a> ChampSelectEndpoint<'a> {
                 headers: None,
                 body: Some(body.to_string()),
             },
+            ChampSelectEndpoint::SessionAction(action_id, body) => EndpointInfo {
+                url: format!("{}/session/actions/{}", CHAMP_SELECT_URL, action_id),
+                method: Method::PATCH,
+                headers: None,
+                body: Some(body.to_string()),
+            },
+            ChampSelectEndpoint::CompleteSessionAction(action_id) => EndpointInfo {
+                url: format!(
+                    "{}/session/actions/{}/complete",
+                    CHAMP_SELECT_URL, action_id
+                ),
+                method: Method::POST,
+                headers: None,
+                body: None,
+            },
+            ChampSelectEndpoint::Trade(trade_id, action) => EndpointInfo {
+                url: format!(
+                    "{}/session/trades/{}/{}",
+                    CHAMP_SELECT_URL,
+                    trade_id,
+                    action.as_str()
+                ),
+                method: Method::POST,
+                headers: None,
+                body: None,
+            },
+            ChampSelectEndpoint::PickOrderSwap(swap_id, action) => EndpointInfo {
+                url: format!(
+                    "{}/session/pick-order-swaps/{}/{}",
+                    CHAMP_SELECT_URL,
+                    swap_id,
+                    action.as_str()
+                ),
+                method: Method::POST,
+                headers: None,
+                body: None,
+            },
+            ChampSelectEndpoint::PositionSwap(swap_id, action) => EndpointInfo {
+                url: format!(
+                    "{}/session/position-swaps/{}/{}",
+                    CHAMP_SELECT_URL,
+                    swap_id,
+                    action.as_str()
+                ),
+                method: Method::POST,
+                headers: None,
+                body: None,
+            },
+            ChampSelectEndpoint::BenchSwap(champion_id) => EndpointInfo {
+                url: format!("{}/session/bench/swap/{}", CHAMP_SELECT_URL, champion_id),
+                method: Method::POST,
+                headers: None,
+                body: None,
+            },
+            ChampSelectEndpoint::Reroll => EndpointInfo {
+                url: format!("{}/session/my-selection/reroll", CHAMP_SELECT_URL),
+                method: Method::POST,
+                headers: None,
+                body: None,
+            },
+            ChampSelectEndpoint::PickableChampionIds => EndpointInfo {
+                url: format!("{}/pickable-champion-ids", CHAMP_SELECT_URL),
+                method: Method::GET,
+                headers: None,
+                body: None,
+            },
+            ChampSelectEndpoint::BannableChampionIds => EndpointInfo {
+                url: format!("{}/bannable-champion-ids", CHAMP_SELECT_URL),
+                method: Method::GET,
+                headers: None,
+                body: None,
+            },
+            ChampSelectEndpoint::DisabledChampionIds => EndpointInfo {
+                url: format!("{}/disabled-champion-ids", CHAMP_SELECT_URL),
+                method: Method::GET,
+                headers: None,
+                body: None,
+            },
+            ChampSelectEndpoint::SkinCarouselSkins => EndpointInfo {
+                url: format!("{}/skin-carousel-skins", CHAMP_SELECT_URL),
+                method: Method::GET,
+                headers: None,
+                body: None,
+            },
+        }
+    }
+}
+
+// What to do with a trade or swap, `Request` starts one and the rest respond to or withdraw it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractAction {
+    Request,
+    Accept,
+    Decline,
+    Cancel,
+}
+
+impl ContractAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContractAction::Request => "request",
+            ContractAction::Accept => "accept",
+            ContractAction::Decline => "decline",
+            ContractAction::Cancel => "cancel",
         }
     }
 }
@@ -40,54 +175,201 @@ pub struct ChampSelectSession {
     pub allow_rerolling: bool,
     pub allow_skin_selection: bool,
     pub bans: Bans,
-    pub bench_champion_ids: Option<Vec<isize>>,
+    pub bench_champion_ids: Option<Vec<ChampionId>>,
     pub bench_enabled: bool,
     pub boostable_skin_count: isize,
     pub chat_details: ChatDetails,
     pub counter: isize,
-    pub game_id: isize,
+    pub game_id: GameId,
     pub has_simultaneous_bans: bool,
     pub has_simultaneous_picks: bool,
     pub is_custom_game: bool,
     pub is_spectating: bool,
-    pub local_player_cell_id: isize,
+    pub local_player_cell_id: CellId,
     pub locked_event_index: isize,
     pub my_team: Vec<PlayerSelection>,
+    // Missing from sessions where swaps are not possible, such as custom games
+    #[serde(default)]
+    pub pick_order_swaps: Vec<SwapContract>,
+    #[serde(default)]
+    pub position_swaps: Vec<SwapContract>,
     pub rerolls_remaining: isize,
     pub skip_champion_select: bool,
     pub their_team: Vec<PlayerSelection>,
     pub timer: Timer,
+    #[serde(default)]
     pub trades: Vec<TradeContract>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl ChampSelectSession {
+    // Every action, in order, that belongs to the local player.
+    pub fn local_player_actions(&self) -> impl Iterator<Item = &Action> {
+        self.actions
+            .iter()
+            .flatten()
+            .flatten()
+            .filter(move |a| a.actor_cell_id == self.local_player_cell_id)
+    }
+
+    // The pick or ban the local player is expected to make right now, if any.
+    pub fn current_action(&self) -> Option<&Action> {
+        self.local_player_actions()
+            .find(|a| a.is_in_progress && !a.completed)
+    }
+
+    pub fn local_player_selection(&self) -> Option<&PlayerSelection> {
+        self.my_team
+            .iter()
+            .find(|p| p.cell_id == self.local_player_cell_id)
+    }
+
+    // Whether the champion is on the ARAM bench and can be taken.
+    pub fn is_on_bench(&self, champion_id: ChampionId) -> bool {
+        self.bench_enabled
+            && self
+                .bench_champion_ids
+                .iter()
+                .flatten()
+                .any(|&id| id == champion_id)
+    }
+
+    // Champions banned so far, by either team.
+    pub fn banned_champion_ids(&self) -> impl Iterator<Item = ChampionId> + '_ {
+        let completed_bans = self
+            .actions
+            .iter()
+            .flatten()
+            .flatten()
+            .filter(|a| a.completed && a.type_field == ActionType::Ban)
+            .map(|a| a.champion_id);
+
+        self.bans
+            .my_team_bans
+            .iter()
+            .chain(self.bans.their_team_bans.iter())
+            .copied()
+            .chain(completed_bans)
+            .filter(|&id| id != ChampionId::NONE)
+    }
+
+    // Champions locked in by players other than the local player.
+    pub fn locked_champion_ids(&self) -> impl Iterator<Item = ChampionId> + '_ {
+        self.locked_picks().map(|a| a.champion_id)
+    }
+
+    fn locked_picks(&self) -> impl Iterator<Item = &Action> + '_ {
+        self.actions.iter().flatten().flatten().filter(|a| {
+            a.completed
+                && a.type_field == ActionType::Pick
+                && a.actor_cell_id != self.local_player_cell_id
+                && a.champion_id != ChampionId::NONE
+        })
+    }
+}
+
+/*
+    The champions the client offers the local player, joined with the session so champions that have been
+    banned or taken since the lists were fetched are ruled out as well.
+*/
+#[derive(Debug)]
+pub struct ChampionAvailability {
+    pub session: ChampSelectSession,
+    pub pickable_champion_ids: Vec<ChampionId>,
+    pub bannable_champion_ids: Vec<ChampionId>,
+    pub disabled_champion_ids: Vec<ChampionId>,
+}
+
+impl ChampionAvailability {
+    pub fn is_pickable(&self, champion_id: ChampionId) -> bool {
+        self.pickable_champion_ids.contains(&champion_id)
+            && !self.disabled_champion_ids.contains(&champion_id)
+            && !self
+                .session
+                .banned_champion_ids()
+                .any(|id| id == champion_id)
+            && !self
+                .session
+                .locked_picks()
+                .any(|a| a.champion_id == champion_id && self.is_exclusive_pick(a))
+    }
+
+    pub fn is_bannable(&self, champion_id: ChampionId) -> bool {
+        self.bannable_champion_ids.contains(&champion_id)
+            && !self.disabled_champion_ids.contains(&champion_id)
+            && !self
+                .session
+                .banned_champion_ids()
+                .any(|id| id == champion_id)
+    }
+
+    /*
+        Whether a locked pick stops the local player from picking the same champion. In blind pick both
+        teams pick at once without seeing each other, so only allies' picks count.
+    */
+    fn is_exclusive_pick(&self, action: &Action) -> bool {
+        !self.session.allow_duplicate_picks
+            && (action.is_ally_action || !self.session.has_simultaneous_picks)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Action {
-    pub actor_cell_id: isize,
-    pub champion_id: isize,
+    pub actor_cell_id: CellId,
+    pub champion_id: ChampionId,
     pub completed: bool,
     pub id: isize,
     pub is_ally_action: bool,
     pub is_in_progress: bool,
     pub pick_turn: Option<isize>,
     #[serde(rename = "type")]
-    pub type_field: String,
+    pub type_field: ActionType,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionType {
+    Ban,
+    Pick,
+    // Reveals the bans of both teams in ten ban drafts, never performed by a player
+    TenBansReveal,
+    #[serde(other)]
+    Unknown,
+}
+
+/*
+    Body of an action PATCH. Setting only the champion hovers it, setting `completed` as well locks it in,
+    for both picks and bans.
+*/
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Bans {
-    pub my_team_bans: Vec<BannedChampions>,
-    pub num_bans: isize,
-    pub their_team_bans: Vec<BannedChampions>,
+pub struct ActionUpdate {
+    pub champion_id: ChampionId,
+    pub completed: bool,
+}
+
+impl ActionUpdate {
+    pub fn hover(champion_id: ChampionId) -> Self {
+        Self {
+            champion_id,
+            completed: false,
+        }
+    }
+
+    pub fn lock_in(champion_id: ChampionId) -> Self {
+        Self {
+            champion_id,
+            completed: true,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct BannedChampions {
-    my_team_bans: Vec<isize>,
-    num_bans: isize,
-    their_team_bans: Vec<isize>,
+pub struct Bans {
+    pub my_team_bans: Vec<ChampionId>,
+    pub num_bans: isize,
+    pub their_team_bans: Vec<ChampionId>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -101,43 +383,83 @@ pub struct ChatDetails {
 #[serde(rename_all = "camelCase")]
 pub struct EntitledFeatureState {
     pub additional_rerolls: isize,
-    pub unlocked_skin_ids: Vec<isize>,
+    pub unlocked_skin_ids: Vec<SkinId>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PlayerSelection {
     pub assigned_position: String,
-    pub cell_id: isize,
-    pub champion_id: isize,
-    pub champion_pick_intent: isize,
-    pub selected_skin_id: isize,
-    pub spell1_id: isize,
-    pub spell2_id: isize,
-    pub summoner_id: isize,
+    pub cell_id: CellId,
+    pub champion_id: ChampionId,
+    pub champion_pick_intent: ChampionId,
+    pub selected_skin_id: SkinId,
+    pub spell1_id: SpellId,
+    pub spell2_id: SpellId,
+    pub summoner_id: SummonerId,
     pub team: isize,
     pub ward_skin_id: isize,
 }
 
+impl PlayerSelection {
+    // `assigned_position` is lowercase and empty in queues without positions.
+    pub fn position(&self) -> LobbyPosition {
+        match self.assigned_position.as_str() {
+            "top" => LobbyPosition::Top,
+            "jungle" => LobbyPosition::Jungle,
+            "middle" => LobbyPosition::Middle,
+            "bottom" => LobbyPosition::Bottom,
+            "utility" => LobbyPosition::Utility,
+            _ => LobbyPosition::Unselected,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Timer {
     pub adjusted_time_left_in_phase: isize,
     pub internal_now_in_epoch_ms: isize,
     pub is_infinite: bool,
-    pub phase: String,
+    pub phase: ChampSelectPhase,
     pub total_time_in_phase: isize,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ChampSelectPhase {
+    Planning,
+    BanPick,
+    Finalization,
+    GameStarting,
+    #[serde(other)]
+    Unknown,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TradeContract {
-    cell_id: isize,
-    id: isize,
-    state: TradeContractState,
+    // The cell of the player on the other side of the trade
+    pub cell_id: CellId,
+    pub id: isize,
+    pub state: TradeContractState,
 }
 
+// A pick order or position swap, shaped like a trade but with its own set of states.
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapContract {
+    // The cell of the player on the other side of the swap
+    pub cell_id: CellId,
+    pub id: isize,
+    pub state: SwapContractState,
+}
+
+/*
+    `Received` is an offer from the other player waiting on us, `Sent` is one of ours waiting on them. The
+    rest describe whether a new request can be made.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TradeContractState {
     Available,
@@ -148,14 +470,80 @@ pub enum TradeContractState {
     Declined,
     Cancelled,
     Accepted,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SwapContractState {
+    Available,
+    Busy,
+    Invalid,
+    Received,
+    Sent,
+    Declined,
+    Cancelled,
+    Accepted,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkinCarouselSkin {
+    pub champion_id: ChampionId,
+    pub child_skins: Vec<SkinCarouselChroma>,
+    pub chroma_preview_path: Option<String>,
+    pub disabled: bool,
+    pub id: SkinId,
+    pub is_champion_unlocked: bool,
+    pub name: String,
+    pub ownership: SkinOwnership,
+    pub splash_path: String,
+    pub tile_path: String,
+    pub unlocked: bool,
+}
+
+impl SkinCarouselSkin {
+    // Whether the skin can be set as `MySelection::selected_skin_id`.
+    pub fn is_selectable(&self) -> bool {
+        self.unlocked && !self.disabled
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkinCarouselChroma {
+    pub champion_id: ChampionId,
+    pub chroma_preview_path: Option<String>,
+    pub colors: Vec<String>,
+    pub disabled: bool,
+    pub id: SkinId,
+    pub name: String,
+    pub parent_skin_id: SkinId,
+    pub unlocked: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkinOwnership {
+    pub owned: bool,
+    pub rental: SkinRental,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkinRental {
+    pub rented: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MySelection {
-    pub selected_skin_id: isize,
-    pub spell1_id: isize,
-    pub spell2_id: isize,
+    pub selected_skin_id: SkinId,
+    pub spell1_id: SpellId,
+    pub spell2_id: SpellId,
     pub ward_skin_id: isize,
 }
 
@@ -172,10 +560,208 @@ impl From<&PlayerSelection> for MySelection {
 
 impl MySelection {
     pub fn set_flash_first(&mut self) {
-        // A spell with ID of 4 == Flash
-        if self.spell1_id != 4 && self.spell2_id == 4 {
+        self.set_spell_first(summoner_spells::FLASH);
+    }
+
+    // Move a spell that is already selected onto the first (D) key.
+    pub fn set_spell_first(&mut self, spell_id: SpellId) {
+        if self.spell1_id != spell_id && self.spell2_id == spell_id {
             self.spell2_id = self.spell1_id;
-            self.spell1_id = 4;
+            self.spell1_id = spell_id;
+        }
+    }
+
+    // Move a spell that is already selected onto the second (F) key.
+    pub fn set_spell_second(&mut self, spell_id: SpellId) {
+        if self.spell2_id != spell_id && self.spell1_id == spell_id {
+            self.spell1_id = self.spell2_id;
+            self.spell2_id = spell_id;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::endpoints::champ_select::summoner_spells::{FLASH, TELEPORT};
+    use crate::endpoints::champ_select::{
+        ActionType, ActionUpdate, ChampSelectEndpoint, ChampSelectSession, ChampionAvailability,
+        ContractAction, MySelection, SkinCarouselSkin, SwapContractState, TradeContractState,
+    };
+    use crate::endpoints::fixtures::assert_round_trip;
+    use crate::endpoints::ids::{CellId, ChampionId, SkinId};
+
+    #[test]
+    fn test_aram_session() {
+        let session: ChampSelectSession = assert_round_trip("champ_select/aram.json");
+
+        assert!(session.bench_enabled);
+        assert_eq!(
+            session.bench_champion_ids,
+            Some(vec![ChampionId(22), ChampionId(119), ChampionId(51)])
+        );
+        assert_eq!(session.rerolls_remaining, 1);
+        assert!(session.is_on_bench(ChampionId(119)));
+        assert!(!session.is_on_bench(ChampionId(157)));
+        assert_eq!(session.actions.map(|a| a.len()), Some(0));
+        assert!(session.pick_order_swaps.is_empty() && session.position_swaps.is_empty());
+    }
+
+    #[test]
+    fn test_ranked_draft_session() {
+        let session: ChampSelectSession = assert_round_trip("champ_select/ranked_draft.json");
+
+        assert_eq!(
+            session.bans.my_team_bans,
+            vec![
+                ChampionId(157),
+                ChampionId(555),
+                ChampionId(777),
+                ChampionId(350)
+            ]
+        );
+        assert_eq!(session.bans.num_bans, 10);
+        assert_eq!(session.their_team.len(), 5);
+        assert_eq!(session.trades[1].state, TradeContractState::Available);
+        assert_eq!(
+            session.pick_order_swaps[1].state,
+            SwapContractState::Received
+        );
+        assert_eq!(session.position_swaps[3].state, SwapContractState::Sent);
+
+        let in_progress = session
+            .actions
+            .iter()
+            .flatten()
+            .flatten()
+            .filter(|a| a.is_in_progress)
+            .count();
+
+        assert_eq!(in_progress, 1);
+
+        // The enemy team is picking, our pick comes next
+        assert_eq!(session.local_player_actions().count(), 2);
+        assert!(session.current_action().is_none());
+        assert_eq!(session.local_player_selection().unwrap().cell_id, CellId(1));
+
+        let mut selection = MySelection::from(&session.my_team[0]);
+        selection.set_flash_first();
+
+        assert_eq!(
+            (selection.spell1_id, selection.spell2_id),
+            (FLASH, TELEPORT)
+        );
+    }
+
+    #[test]
+    fn test_custom_session() {
+        let session: ChampSelectSession = assert_round_trip("champ_select/custom.json");
+
+        assert!(session.is_custom_game);
+        assert_eq!(session.their_team.len(), 1);
+
+        let action = session.current_action().unwrap();
+
+        assert_eq!(action.id, 1);
+        assert_eq!(action.type_field, ActionType::Pick);
+    }
+
+    #[test]
+    fn test_action_update_body() {
+        assert_eq!(
+            serde_json::to_string(&ActionUpdate::lock_in(ChampionId(99))).unwrap(),
+            r#"{"championId":99,"completed":true}"#
+        );
+    }
+
+    #[test]
+    fn test_champion_availability() {
+        let mut availability = ChampionAvailability {
+            session: assert_round_trip("champ_select/ranked_draft.json"),
+            pickable_champion_ids: vec![
+                ChampionId(1),
+                ChampionId(64),
+                ChampionId(86),
+                ChampionId(89),
+                ChampionId(103),
+                ChampionId(157),
+                ChampionId(266),
+            ],
+            bannable_champion_ids: vec![
+                ChampionId(1),
+                ChampionId(64),
+                ChampionId(86),
+                ChampionId(89),
+                ChampionId(103),
+                ChampionId(157),
+                ChampionId(266),
+                ChampionId(555),
+            ],
+            disabled_champion_ids: vec![ChampionId(1)],
+        };
+
+        // Free to pick
+        assert!(availability.is_pickable(ChampionId(103)));
+        // Not owned
+        assert!(!availability.is_pickable(ChampionId(555)));
+        // Disabled by the client
+        assert!(!availability.is_pickable(ChampionId(1)));
+        // Banned
+        assert!(!availability.is_pickable(ChampionId(157)));
+        assert!(!availability.is_pickable(ChampionId(64)));
+        // Locked in by an ally and an enemy
+        assert!(!availability.is_pickable(ChampionId(266)));
+        assert!(!availability.is_pickable(ChampionId(86)));
+
+        assert!(availability.is_bannable(ChampionId(266)));
+        assert!(!availability.is_bannable(ChampionId(555)));
+
+        // Blind pick, where only the ally lock counts
+        availability.session.has_simultaneous_picks = true;
+
+        assert!(!availability.is_pickable(ChampionId(266)));
+        assert!(availability.is_pickable(ChampionId(86)));
+
+        availability.session.allow_duplicate_picks = true;
+
+        assert!(availability.is_pickable(ChampionId(266)));
+        assert!(availability.is_pickable(ChampionId(86)));
+    }
+
+    #[test]
+    fn test_skin_carousel() {
+        let skins: Vec<SkinCarouselSkin> = assert_round_trip("champ_select/skin_carousel.json");
+
+        let selectable: Vec<SkinId> = skins
+            .iter()
+            .filter(|s| s.is_selectable())
+            .map(|s| s.id)
+            .collect();
+
+        assert_eq!(selectable, vec![SkinId(103000), SkinId(103014)]);
+        assert_eq!(skins[1].child_skins[0].parent_skin_id, SkinId(103014));
+    }
+
+    #[test]
+    fn test_contract_urls() {
+        assert_eq!(
+            ChampSelectEndpoint::Trade(2, ContractAction::Accept)
+                .info()
+                .url,
+            "/lol-champ-select/v1/session/trades/2/accept"
+        );
+        assert_eq!(
+            ChampSelectEndpoint::PositionSwap(6, ContractAction::Request)
+                .info()
+                .url,
+            "/lol-champ-select/v1/session/position-swaps/6/request"
+        );
+    }
+
+    #[test]
+    fn test_practice_tool_session() {
+        let session: ChampSelectSession = assert_round_trip("champ_select/practice_tool.json");
+
+        assert!(session.allow_duplicate_picks);
+        assert!(session.timer.is_infinite);
+    }
+}
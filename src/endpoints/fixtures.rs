@@ -0,0 +1,103 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::endpoints::game_data::{PerkData, PerkStyle};
+use crate::endpoints::perks::{PerksPage, PerksPageBuilder};
+
+/*
+    Helpers for the captured responses in `test_data/`. Fixtures are real payloads, so they usually
+    contain more fields than the models know about. A round trip therefore checks that every field
+    the model serializes matches the fixture, rather than that the two are identical.
+*/
+pub(crate) fn load_fixture(path: &str) -> Value {
+    let contents = std::fs::read_to_string(format!("./test_data/{}", path))
+        .unwrap_or_else(|e| panic!("Failed to read fixture {}: {}", path, e));
+
+    serde_json::from_str(&contents).unwrap_or_else(|e| panic!("Invalid fixture {}: {}", path, e))
+}
+
+// The captured perk styles and perks that perks pages are checked against.
+pub(crate) fn perk_fixtures() -> (Vec<PerkStyle>, Vec<PerkData>) {
+    (
+        assert_round_trip("perks/styles.json"),
+        assert_round_trip("perks/perks.json"),
+    )
+}
+
+pub(crate) fn build_perks_page(builder: PerksPageBuilder) -> crate::Result<PerksPage> {
+    let (styles, perks) = perk_fixtures();
+
+    builder.build(&styles, &perks)
+}
+
+// Find an action by ID across every turn of a champ select session fixture.
+pub(crate) fn session_action(session: &mut Value, id: isize) -> &mut Value {
+    session["actions"]
+        .as_array_mut()
+        .expect("Session fixture has no actions")
+        .iter_mut()
+        .flat_map(|turn| {
+            turn.as_array_mut()
+                .expect("Action turn is not an array")
+                .iter_mut()
+        })
+        .find(|a| a["id"] == id)
+        .unwrap_or_else(|| panic!("Session fixture has no action {}", id))
+}
+
+/*
+    Deserialize a fixture into `T`, serialize it back and check that nothing was lost or changed
+    along the way. Returns the deserialized model for further assertions.
+*/
+pub(crate) fn assert_round_trip<T: Serialize + DeserializeOwned>(path: &str) -> T {
+    let fixture = load_fixture(path);
+
+    let model: T = serde_json::from_value(fixture.clone())
+        .unwrap_or_else(|e| panic!("Failed to deserialize fixture {}: {}", path, e));
+
+    let serialized = serde_json::to_value(&model).expect("Failed to serialize model");
+
+    if let Err(field) = is_subset(&serialized, &fixture, String::new()) {
+        panic!("Fixture {} did not round trip at `{}`", path, field);
+    }
+
+    // The serialized form must itself be readable again
+    let reparsed: T = serde_json::from_value(serialized.clone())
+        .unwrap_or_else(|e| panic!("Failed to deserialize serialized {}: {}", path, e));
+
+    assert_eq!(
+        serde_json::to_value(&reparsed).expect("Failed to serialize model"),
+        serialized
+    );
+
+    model
+}
+
+// A null in the model is allowed to stand in for a field that the fixture does not have.
+fn is_subset(model: &Value, fixture: &Value, path: String) -> Result<(), String> {
+    match (model, fixture) {
+        (Value::Object(model), Value::Object(fixture)) => {
+            for (key, value) in model {
+                let field = format!("{}.{}", path, key);
+
+                match fixture.get(key) {
+                    Some(expected) => is_subset(value, expected, field)?,
+                    None if value.is_null() => {}
+                    None => return Err(field),
+                }
+            }
+
+            Ok(())
+        }
+        (Value::Array(model), Value::Array(fixture)) if model.len() == fixture.len() => model
+            .iter()
+            .zip(fixture)
+            .enumerate()
+            .try_for_each(|(i, (value, expected))| {
+                is_subset(value, expected, format!("{}[{}]", path, i))
+            }),
+        _ if model == fixture => Ok(()),
+        _ => Err(path),
+    }
+}
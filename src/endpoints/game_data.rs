@@ -0,0 +1,200 @@
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::endpoints::ids::{ChampionId, PerkId, PerkStyleId, SpellId};
+use crate::endpoints::EndpointInfo;
+
+pub const GAME_DATA_ASSETS_URL: &str = "/lol-game-data/assets";
+
+#[allow(unused)]
+pub enum GameDataEndpoint<'a> {
+    Asset(&'a str),
+    ChampionSummary,
+    SummonerSpells,
+    Perks,
+    PerkStyles,
+}
+
+impl<'a> GameDataEndpoint<'a> {
+    pub fn info(&self) -> EndpointInfo {
+        match self {
+            GameDataEndpoint::Asset(path) => EndpointInfo {
+                url: format!(
+                    "{}/{}",
+                    GAME_DATA_ASSETS_URL,
+                    normalize_asset_path(path).unwrap_or(path)
+                ),
+                method: Method::GET,
+                headers: None,
+                body: None,
+            },
+            GameDataEndpoint::ChampionSummary => EndpointInfo {
+                url: format!("{}/v1/champion-summary.json", GAME_DATA_ASSETS_URL),
+                method: Method::GET,
+                headers: None,
+                body: None,
+            },
+            GameDataEndpoint::SummonerSpells => EndpointInfo {
+                url: format!("{}/v1/summoner-spells.json", GAME_DATA_ASSETS_URL),
+                method: Method::GET,
+                headers: None,
+                body: None,
+            },
+            GameDataEndpoint::Perks => EndpointInfo {
+                url: format!("{}/v1/perks.json", GAME_DATA_ASSETS_URL),
+                method: Method::GET,
+                headers: None,
+                body: None,
+            },
+            GameDataEndpoint::PerkStyles => EndpointInfo {
+                url: format!("{}/v1/perkstyles.json", GAME_DATA_ASSETS_URL),
+                method: Method::GET,
+                headers: None,
+                body: None,
+            },
+        }
+    }
+}
+
+/*
+    Asset paths returned by the client are absolute (e.g. "/lol-game-data/assets/v1/champion-icons/1.png")
+    while users will often pass them relative to the assets root. Both forms are normalized to the
+    relative form. Paths that try to escape the assets root are rejected.
+*/
+pub fn normalize_asset_path(path: &str) -> Option<&str> {
+    let relative = path
+        .trim_start_matches('/')
+        .trim_start_matches(GAME_DATA_ASSETS_URL.trim_start_matches('/'))
+        .trim_start_matches('/');
+
+    if relative.is_empty() || relative.split(['/', '\\']).any(|segment| segment == "..") {
+        return None;
+    }
+
+    Some(relative)
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct DownloadProgress {
+    pub downloaded: u64,
+    pub total: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampionSummary {
+    // The champion's internal name, e.g. "MonkeyKing" for Wukong
+    pub alias: String,
+    pub id: ChampionId,
+    pub name: String,
+    pub roles: Vec<String>,
+    pub square_portrait_path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SummonerSpellData {
+    // Seconds
+    pub cooldown: isize,
+    pub description: String,
+    pub game_modes: Vec<String>,
+    pub icon_path: String,
+    pub id: SpellId,
+    pub name: String,
+    pub summoner_level: isize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerkData {
+    pub icon_path: String,
+    pub id: PerkId,
+    pub long_desc: String,
+    pub name: String,
+    pub short_desc: String,
+    pub tooltip: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerkStyles {
+    pub schema_version: isize,
+    pub styles: Vec<PerkStyle>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerkStyle {
+    pub allowed_sub_styles: Vec<PerkStyleId>,
+    pub default_page_name: String,
+    pub default_perks: Vec<PerkId>,
+    pub default_sub_style: PerkStyleId,
+    pub icon_path: String,
+    pub id: PerkStyleId,
+    pub is_advanced: bool,
+    pub name: String,
+    pub slots: Vec<PerkSlot>,
+    pub tooltip: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerkSlot {
+    pub perks: Vec<PerkId>,
+    pub slot_label: String,
+    #[serde(rename = "type")]
+    pub type_field: PerkSlotType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PerkSlotType {
+    #[serde(rename = "kKeyStone")]
+    KeyStone,
+    #[serde(rename = "kMixedRegularSplashable")]
+    MixedRegularSplashable,
+    #[serde(rename = "kStatMod")]
+    StatMod,
+    #[serde(other)]
+    Unknown,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endpoints::fixtures::assert_round_trip;
+
+    #[test]
+    fn test_game_data_fixtures() {
+        let champions: Vec<ChampionSummary> = assert_round_trip("game_data/champion_summary.json");
+        let spells: Vec<SummonerSpellData> = assert_round_trip("game_data/summoner_spells.json");
+        let perks: Vec<PerkData> = assert_round_trip("game_data/perks.json");
+        let styles: PerkStyles = assert_round_trip("game_data/perkstyles.json");
+
+        assert_eq!(champions[2].alias, "MonkeyKing");
+        assert_eq!(spells[0].name, "Flash");
+        assert_eq!(perks[1].id, PerkId(8112));
+        assert_eq!(styles.styles[0].slots[1].type_field, PerkSlotType::StatMod);
+    }
+
+    #[test]
+    fn normalize_absolute_asset_path() {
+        assert_eq!(
+            normalize_asset_path("/lol-game-data/assets/v1/champion-icons/1.png"),
+            Some("v1/champion-icons/1.png")
+        );
+    }
+
+    #[test]
+    fn normalize_relative_asset_path() {
+        assert_eq!(
+            normalize_asset_path("v1/champion-icons/1.png"),
+            Some("v1/champion-icons/1.png")
+        );
+    }
+
+    #[test]
+    fn normalize_rejects_traversal() {
+        assert_eq!(normalize_asset_path("v1/../../lockfile"), None);
+        assert_eq!(normalize_asset_path("/lol-game-data/assets/"), None);
+    }
+}
@@ -1,6 +1,8 @@
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
 
+use crate::endpoints::champ_select::summoner_spells;
+use crate::endpoints::ids::{GameId, QueueId, SpellId, SummonerId};
 use crate::endpoints::EndpointInfo;
 
 const CHAMP_SELECT_URL: &str = "/lol-gameflow/v1";
@@ -8,6 +10,7 @@ const CHAMP_SELECT_URL: &str = "/lol-gameflow/v1";
 #[allow(unused)]
 pub enum GameFlowEndpoint {
     Session,
+    Phase,
 }
 
 impl GameFlowEndpoint {
@@ -19,6 +22,12 @@ impl GameFlowEndpoint {
                 headers: None,
                 body: None,
             },
+            GameFlowEndpoint::Phase => EndpointInfo {
+                url: format!("{}/gameflow-phase", CHAMP_SELECT_URL),
+                method: Method::GET,
+                headers: None,
+                body: None,
+            },
         }
     }
 }
@@ -33,7 +42,7 @@ pub struct GameFlowSession {
     pub phase: GameFlowPhase,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GameFlowPhase {
     None,
     Lobby,
@@ -65,7 +74,7 @@ pub struct GameClient {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GameData {
-    pub game_id: isize,
+    pub game_id: GameId,
     pub game_name: String,
     pub is_custom_game: bool,
     pub password: String,
@@ -88,7 +97,7 @@ pub struct Queue {
     pub detailed_description: String,
     pub game_mode: String,
     pub game_type_config: GameTypeConfig,
-    pub id: isize,
+    pub id: QueueId,
     pub is_ranked: bool,
     pub is_team_builder_managed: bool,
     pub is_team_only: Option<bool>,
@@ -150,9 +159,20 @@ pub struct QueueRewards {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GameDodge {
-    pub dodge_ids: Vec<::serde_json::Value>,
-    pub phase: String,
-    pub state: String,
+    pub dodge_ids: Vec<SummonerId>,
+    // The phase the game was dodged in
+    pub phase: GameFlowPhase,
+    pub state: DodgeState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DodgeState {
+    Invalid,
+    PartyDodged,
+    StrangerDodged,
+    TournamentDodged,
+    #[serde(other)]
+    Unknown,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -178,7 +198,7 @@ pub struct Map {
     pub properties: Properties,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum GameMode {
     Classic,
@@ -196,10 +216,10 @@ impl std::default::Default for GameMode {
 }
 
 impl GameMode {
-    pub fn disallowed_summoner_spells(&self) -> Option<Vec<isize>> {
+    pub fn disallowed_summoner_spells(&self) -> Option<Vec<SpellId>> {
         match self {
             GameMode::Classic | GameMode::PracticeTool | GameMode::NexusBlitz => None,
-            GameMode::Aram => Some(vec![11, 12]),
+            GameMode::Aram => Some(vec![summoner_spells::SMITE, summoner_spells::TELEPORT]),
             GameMode::Unknown => Some(vec![]),
         }
     }
@@ -283,3 +303,51 @@ pub struct PerPositionRequiredSummonerSpells {}
 pub struct Properties {
     pub suppress_runes_masteries_perks: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::endpoints::champ_select::summoner_spells::{SMITE, TELEPORT};
+    use crate::endpoints::fixtures::assert_round_trip;
+    use crate::endpoints::gameflow::{GameFlowPhase, GameFlowSession, GameMode};
+    use crate::endpoints::ids::QueueId;
+
+    #[test]
+    fn test_aram_session() {
+        let session: GameFlowSession = assert_round_trip("gameflow/aram.json");
+
+        assert!(matches!(session.phase, GameFlowPhase::ChampSelect));
+        assert!(matches!(session.map.game_mode, GameMode::Aram));
+        assert_eq!(session.game_data.queue.id, QueueId(450));
+        assert_eq!(
+            session.map.game_mode.disallowed_summoner_spells(),
+            Some(vec![SMITE, TELEPORT])
+        );
+    }
+
+    #[test]
+    fn test_ranked_draft_session() {
+        let session: GameFlowSession = assert_round_trip("gameflow/ranked_draft.json");
+
+        assert!(matches!(session.phase, GameFlowPhase::InProgress));
+        assert!(matches!(session.map.game_mode, GameMode::Classic));
+        assert!(session.game_data.queue.is_ranked);
+        assert!(session.game_client.running);
+    }
+
+    #[test]
+    fn test_custom_session() {
+        let session: GameFlowSession = assert_round_trip("gameflow/custom.json");
+
+        assert!(matches!(session.phase, GameFlowPhase::Lobby));
+        assert!(session.game_data.is_custom_game);
+        assert_eq!(session.game_data.queue.id, QueueId(-1));
+    }
+
+    #[test]
+    fn test_practice_tool_session() {
+        let session: GameFlowSession = assert_round_trip("gameflow/practice_tool.json");
+
+        assert!(matches!(session.map.game_mode, GameMode::PracticeTool));
+        assert!(session.game_data.is_custom_game);
+    }
+}
@@ -0,0 +1,78 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/*
+    IDs are serialized exactly like the number they wrap, so they can be used anywhere the client sends
+    or expects a plain integer. Types the client uses -1 as a placeholder for are signed.
+*/
+macro_rules! id_type {
+    ($(#[$meta:meta])* $name:ident($inner:ty)) => {
+        $(#[$meta])*
+        #[derive(
+            Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+        )]
+        #[serde(transparent)]
+        pub struct $name(pub $inner);
+
+        impl $name {
+            pub const fn get(self) -> $inner {
+                self.0
+            }
+        }
+
+        impl From<$inner> for $name {
+            fn from(id: $inner) -> Self {
+                Self(id)
+            }
+        }
+
+        impl From<$name> for $inner {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    };
+}
+
+id_type!(ChampionId(i32));
+id_type!(SummonerId(u64));
+id_type!(SkinId(i32));
+id_type!(SpellId(u32));
+id_type!(PerkId(u32));
+id_type!(PerkStyleId(u32));
+id_type!(
+    // Custom games use -1
+    QueueId(i32)
+);
+id_type!(GameId(u64));
+id_type!(
+    // A player's slot in champ select, 0-4 for the blue side and 5-9 for the red side
+    CellId(i32)
+);
+
+impl ChampionId {
+    // Sent while no champion has been hovered or picked yet
+    pub const NONE: Self = Self(0);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::endpoints::ids::{ChampionId, SummonerId};
+
+    #[test]
+    fn test_ids_are_transparent() {
+        assert_eq!(serde_json::to_string(&ChampionId(103)).unwrap(), "103");
+        assert_eq!(
+            serde_json::from_str::<Vec<SummonerId>>("[2611938]").unwrap(),
+            vec![SummonerId(2611938)]
+        );
+        assert!(serde_json::from_str::<SummonerId>("-1").is_err());
+    }
+}
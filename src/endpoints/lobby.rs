@@ -0,0 +1,233 @@
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::endpoints::ids::{QueueId, SummonerId};
+use crate::endpoints::EndpointInfo;
+
+const LOBBY_URL: &str = "/lol-lobby/v2/lobby";
+
+#[allow(unused)]
+pub enum LobbyEndpoint<'a> {
+    Lobby,
+    CreateLobby(&'a str),
+    LeaveLobby,
+    PositionPreferences(&'a str),
+    Invitations,
+    SendInvitations(&'a str),
+    KickMember(SummonerId),
+    PromoteMember(SummonerId),
+    StartSearch,
+    CancelSearch,
+}
+
+impl<'a> LobbyEndpoint<'a> {
+    pub fn info(&self) -> EndpointInfo {
+        match self {
+            LobbyEndpoint::Lobby => EndpointInfo {
+                url: LOBBY_URL.to_owned(),
+                method: Method::GET,
+                headers: None,
+                body: None,
+            },
+            LobbyEndpoint::CreateLobby(body) => EndpointInfo {
+                url: LOBBY_URL.to_owned(),
+                method: Method::POST,
+                headers: None,
+                body: Some(body.to_string()),
+            },
+            LobbyEndpoint::LeaveLobby => EndpointInfo {
+                url: LOBBY_URL.to_owned(),
+                method: Method::DELETE,
+                headers: None,
+                body: None,
+            },
+            LobbyEndpoint::PositionPreferences(body) => EndpointInfo {
+                url: format!("{}/members/localMember/position-preferences", LOBBY_URL),
+                method: Method::PUT,
+                headers: None,
+                body: Some(body.to_string()),
+            },
+            LobbyEndpoint::Invitations => EndpointInfo {
+                url: format!("{}/invitations", LOBBY_URL),
+                method: Method::GET,
+                headers: None,
+                body: None,
+            },
+            LobbyEndpoint::SendInvitations(body) => EndpointInfo {
+                url: format!("{}/invitations", LOBBY_URL),
+                method: Method::POST,
+                headers: None,
+                body: Some(body.to_string()),
+            },
+            LobbyEndpoint::KickMember(summoner_id) => EndpointInfo {
+                url: format!("{}/members/{}/kick", LOBBY_URL, summoner_id),
+                method: Method::POST,
+                headers: None,
+                body: None,
+            },
+            LobbyEndpoint::PromoteMember(summoner_id) => EndpointInfo {
+                url: format!("{}/members/{}/promote", LOBBY_URL, summoner_id),
+                method: Method::POST,
+                headers: None,
+                body: None,
+            },
+            LobbyEndpoint::StartSearch => EndpointInfo {
+                url: format!("{}/matchmaking/search", LOBBY_URL),
+                method: Method::POST,
+                headers: None,
+                body: None,
+            },
+            LobbyEndpoint::CancelSearch => EndpointInfo {
+                url: format!("{}/matchmaking/search", LOBBY_URL),
+                method: Method::DELETE,
+                headers: None,
+                body: None,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Lobby {
+    pub can_start_activity: bool,
+    pub chat_room_id: String,
+    pub game_config: LobbyGameConfig,
+    pub invitations: Vec<LobbyInvitation>,
+    pub local_member: LobbyMember,
+    pub members: Vec<LobbyMember>,
+    pub party_id: String,
+    pub party_type: String,
+    pub scarce_positions: Vec<LobbyPosition>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbyGameConfig {
+    pub allowable_premade_sizes: Vec<isize>,
+    pub custom_lobby_name: String,
+    pub game_mode: String,
+    pub is_custom: bool,
+    pub is_lobby_full: bool,
+    pub is_team_builder_managed: bool,
+    pub map_id: isize,
+    pub max_lobby_size: isize,
+    pub max_team_size: isize,
+    pub premade_size_allowed: bool,
+    pub queue_id: QueueId,
+    pub show_position_selector: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbyMember {
+    pub allowed_change_activity: bool,
+    pub allowed_invite_others: bool,
+    pub allowed_kick_others: bool,
+    pub allowed_start_activity: bool,
+    pub allowed_toggle_invite: bool,
+    pub first_position_preference: LobbyPosition,
+    pub is_bot: bool,
+    pub is_leader: bool,
+    pub is_spectator: bool,
+    pub puuid: String,
+    pub ready: bool,
+    pub second_position_preference: LobbyPosition,
+    pub summoner_icon_id: isize,
+    pub summoner_id: SummonerId,
+    pub summoner_internal_name: String,
+    pub summoner_level: isize,
+    pub summoner_name: String,
+    pub team_id: isize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbyInvitation {
+    pub invitation_id: String,
+    pub state: InvitationState,
+    pub timestamp: String,
+    pub to_summoner_id: SummonerId,
+    pub to_summoner_name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LobbyPosition {
+    Top,
+    Jungle,
+    Middle,
+    Bottom,
+    Utility,
+    Fill,
+    // Also used by the client when the queue has no position selection
+    #[serde(other)]
+    Unselected,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InvitationState {
+    Requested,
+    Pending,
+    Accepted,
+    Joined,
+    Declined,
+    Kicked,
+    OnHold,
+    Error,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateLobby {
+    pub queue_id: QueueId,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionPreferences {
+    pub first_preference: LobbyPosition,
+    pub second_preference: LobbyPosition,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvitationRequest {
+    pub to_summoner_id: SummonerId,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::endpoints::fixtures::assert_round_trip;
+    use crate::endpoints::ids::QueueId;
+    use crate::endpoints::lobby::{InvitationState, Lobby, LobbyPosition, PositionPreferences};
+
+    #[test]
+    fn test_ranked_lobby() {
+        let lobby: Lobby = assert_round_trip("lobby/ranked_duo.json");
+
+        assert_eq!(lobby.game_config.queue_id, QueueId(420));
+        assert_eq!(lobby.members.len(), 2);
+        assert!(lobby.local_member.is_leader);
+        assert_eq!(
+            lobby.local_member.first_position_preference,
+            LobbyPosition::Jungle
+        );
+        assert_eq!(lobby.invitations[1].state, InvitationState::Pending);
+    }
+
+    #[test]
+    fn test_position_preferences_body() {
+        let preferences = PositionPreferences {
+            first_preference: LobbyPosition::Utility,
+            second_preference: LobbyPosition::Fill,
+        };
+
+        assert_eq!(
+            serde_json::to_string(&preferences).unwrap(),
+            r#"{"firstPreference":"UTILITY","secondPreference":"FILL"}"#
+        );
+    }
+}
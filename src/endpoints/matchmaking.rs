@@ -0,0 +1,240 @@
+use std::time::Duration;
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::endpoints::gameflow::DodgeState;
+use crate::endpoints::ids::{QueueId, SummonerId};
+use crate::endpoints::EndpointInfo;
+
+const MATCHMAKING_URL: &str = "/lol-matchmaking/v1";
+
+#[allow(unused)]
+pub enum MatchmakingEndpoint {
+    Search,
+    ReadyCheck,
+    AcceptReadyCheck,
+    DeclineReadyCheck,
+}
+
+impl MatchmakingEndpoint {
+    pub fn info(&self) -> EndpointInfo {
+        match self {
+            MatchmakingEndpoint::Search => EndpointInfo {
+                url: format!("{}/search", MATCHMAKING_URL),
+                method: Method::GET,
+                headers: None,
+                body: None,
+            },
+            MatchmakingEndpoint::ReadyCheck => EndpointInfo {
+                url: format!("{}/ready-check", MATCHMAKING_URL),
+                method: Method::GET,
+                headers: None,
+                body: None,
+            },
+            MatchmakingEndpoint::AcceptReadyCheck => EndpointInfo {
+                url: format!("{}/ready-check/accept", MATCHMAKING_URL),
+                method: Method::POST,
+                headers: None,
+                body: None,
+            },
+            MatchmakingEndpoint::DeclineReadyCheck => EndpointInfo {
+                url: format!("{}/ready-check/decline", MATCHMAKING_URL),
+                method: Method::POST,
+                headers: None,
+                body: None,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchmakingSearch {
+    pub dodge_data: DodgeData,
+    pub errors: Vec<SearchError>,
+    // Seconds
+    pub estimated_queue_time: f64,
+    pub is_currently_in_queue: bool,
+    pub lobby_id: String,
+    pub low_priority_data: LowPriorityData,
+    pub queue_id: QueueId,
+    pub ready_check: ReadyCheck,
+    pub search_state: SearchState,
+    // Seconds
+    pub time_in_queue: f64,
+}
+
+impl MatchmakingSearch {
+    pub fn estimated_queue_time(&self) -> Duration {
+        seconds(self.estimated_queue_time)
+    }
+
+    pub fn time_in_queue(&self) -> Duration {
+        seconds(self.time_in_queue)
+    }
+
+    /*
+        The longest penalty that is keeping the lobby out of the queue, either low priority queue or a
+        penalty attached to a search error such as a queue dodge.
+    */
+    pub fn penalty_remaining(&self) -> Option<Duration> {
+        self.errors
+            .iter()
+            .map(|e| e.penalty_time_remaining)
+            .chain(std::iter::once(
+                self.low_priority_data.penalty_time_remaining,
+            ))
+            .filter(|remaining| *remaining > 0.0)
+            .reduce(f64::max)
+            .map(seconds)
+    }
+}
+
+fn seconds(seconds: f64) -> Duration {
+    Duration::try_from_secs_f64(seconds).unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SearchState {
+    Invalid,
+    AbandonedLowPriorityQueue,
+    Canceled,
+    Searching,
+    Found,
+    Error,
+    ServiceError,
+    ServiceShutdown,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchError {
+    // e.g. "QUEUE_DODGER" or "LEAVER_BUSTED"
+    pub error_type: String,
+    pub id: isize,
+    pub message: String,
+    pub penalized_summoner_id: SummonerId,
+    // Seconds
+    pub penalty_time_remaining: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LowPriorityData {
+    pub busted_leaver_access_token: String,
+    pub penalized_summoner_ids: Vec<SummonerId>,
+    // Seconds
+    pub penalty_time: f64,
+    // Seconds
+    pub penalty_time_remaining: f64,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DodgeData {
+    pub dodger_id: SummonerId,
+    pub state: DodgeState,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadyCheck {
+    pub decliner_ids: Vec<SummonerId>,
+    pub dodge_warning: DodgeWarning,
+    pub player_response: ReadyCheckResponse,
+    pub state: ReadyCheckState,
+    pub suppress_ux: bool,
+    // Seconds since the ready check started
+    pub timer: f64,
+}
+
+impl ReadyCheck {
+    // Whether the local player still has to accept or decline.
+    pub fn awaiting_response(&self) -> bool {
+        self.state == ReadyCheckState::InProgress
+            && self.player_response == ReadyCheckResponse::None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReadyCheckState {
+    Invalid,
+    InProgress,
+    EveryoneReady,
+    StrangerNotReady,
+    PartyNotReady,
+    Error,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReadyCheckResponse {
+    None,
+    Accepted,
+    Declined,
+}
+
+// Shown to players who are about to receive a queue penalty for declining.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DodgeWarning {
+    None,
+    Warning,
+    Penalty,
+    #[serde(other)]
+    Unknown,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::endpoints::fixtures::assert_round_trip;
+    use crate::endpoints::gameflow::DodgeState;
+    use crate::endpoints::ids::SummonerId;
+    use crate::endpoints::matchmaking::{
+        DodgeWarning, MatchmakingSearch, ReadyCheck, ReadyCheckState, SearchState,
+    };
+
+    #[test]
+    fn test_search_in_progress() {
+        let search: MatchmakingSearch = assert_round_trip("matchmaking/search.json");
+
+        assert_eq!(search.search_state, SearchState::Searching);
+        assert_eq!(search.time_in_queue(), Duration::from_secs(42));
+        assert_eq!(search.penalty_remaining(), None);
+        assert_eq!(search.dodge_data.state, DodgeState::Invalid);
+    }
+
+    #[test]
+    fn test_search_low_priority_queue() {
+        let search: MatchmakingSearch = assert_round_trip("matchmaking/search_penalty.json");
+
+        assert_eq!(search.search_state, SearchState::Error);
+        assert_eq!(search.errors[0].error_type, "QUEUE_DODGER");
+        assert_eq!(search.penalty_remaining(), Some(Duration::from_secs(300)));
+        assert_eq!(search.dodge_data.state, DodgeState::StrangerDodged);
+    }
+
+    #[test]
+    fn test_ready_check_in_progress() {
+        let ready_check: ReadyCheck = assert_round_trip("matchmaking/ready_check.json");
+
+        assert!(ready_check.awaiting_response());
+        assert_eq!(ready_check.dodge_warning, DodgeWarning::None);
+    }
+
+    #[test]
+    fn test_ready_check_declined() {
+        let ready_check: ReadyCheck = assert_round_trip("matchmaking/ready_check_declined.json");
+
+        assert!(!ready_check.awaiting_response());
+        assert_eq!(ready_check.state, ReadyCheckState::StrangerNotReady);
+        assert_eq!(ready_check.decliner_ids, vec![SummonerId(90412785)]);
+        assert_eq!(ready_check.dodge_warning, DodgeWarning::Warning);
+    }
+}
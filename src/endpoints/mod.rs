@@ -2,13 +2,40 @@ use reqwest::header::HeaderMap;
 use reqwest::Method;
 
 pub mod champ_select;
+#[cfg(test)]
+pub(crate) mod fixtures;
+pub mod game_data;
 pub mod gameflow;
+pub mod ids;
+pub mod lobby;
+pub mod matchmaking;
+pub mod patch;
 pub mod perks;
 pub mod summoner;
 
+#[derive(Debug, Clone)]
 pub struct EndpointInfo {
     pub url: String,
     pub method: Method,
     pub headers: Option<HeaderMap>,
     pub body: Option<String>,
 }
+
+/*
+    Append the query parameters that have a value to `url`, encoding them as a form would. Used by
+    the bindings `lcu_codegen` generates.
+*/
+pub fn url_with_query(url: String, params: &[(&str, Option<String>)]) -> String {
+    let mut query = url::form_urlencoded::Serializer::new(String::new());
+
+    for (name, value) in params {
+        if let Some(value) = value {
+            query.append_pair(name, value);
+        }
+    }
+
+    match query.finish() {
+        query if query.is_empty() => url,
+        query => format!("{}?{}", url, query),
+    }
+}
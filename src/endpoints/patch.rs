@@ -0,0 +1,23 @@
+use reqwest::Method;
+
+use crate::endpoints::EndpointInfo;
+
+const PATCH_URL: &str = "/lol-patch/v1";
+
+#[allow(unused)]
+pub enum PatchEndpoint {
+    GameVersion,
+}
+
+impl PatchEndpoint {
+    pub fn info(&self) -> EndpointInfo {
+        match self {
+            PatchEndpoint::GameVersion => EndpointInfo {
+                url: format!("{}/game-version", PATCH_URL),
+                method: Method::GET,
+                headers: None,
+                body: None,
+            },
+        }
+    }
+}
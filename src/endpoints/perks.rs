@@ -1,7 +1,11 @@
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
 
+use crate::endpoints::game_data::{PerkData, PerkSlotType, PerkStyle};
+use crate::endpoints::ids::{PerkId, PerkStyleId};
 use crate::endpoints::EndpointInfo;
+use crate::errors::LcuDriverError;
+use crate::Result;
 
 const PERKS_URL: &str = "/lol-perks/v1";
 
@@ -9,7 +13,10 @@ const PERKS_URL: &str = "/lol-perks/v1";
 pub enum PerksEndpoint {
     Inventory,
     Pages(Method, Option<String>),
-    PagesId(Method, isize),
+    PagesId(Method, isize, Option<String>),
+    CurrentPage(Method, Option<String>),
+    Styles,
+    Perks,
 }
 
 impl PerksEndpoint {
@@ -27,10 +34,28 @@ impl PerksEndpoint {
                 headers: None,
                 body: body.to_owned(),
             },
-            PerksEndpoint::PagesId(method, id) => EndpointInfo {
+            PerksEndpoint::PagesId(method, id, body) => EndpointInfo {
                 url: format!("{}/pages/{}", PERKS_URL, id),
                 method: method.to_owned(),
                 headers: None,
+                body: body.to_owned(),
+            },
+            PerksEndpoint::CurrentPage(method, body) => EndpointInfo {
+                url: format!("{}/currentpage", PERKS_URL),
+                method: method.to_owned(),
+                headers: None,
+                body: body.to_owned(),
+            },
+            PerksEndpoint::Styles => EndpointInfo {
+                url: format!("{}/styles", PERKS_URL),
+                method: Method::GET,
+                headers: None,
+                body: None,
+            },
+            PerksEndpoint::Perks => EndpointInfo {
+                url: format!("{}/perks", PERKS_URL),
+                method: Method::GET,
+                headers: None,
                 body: None,
             },
         }
@@ -48,10 +73,41 @@ pub struct PerksPages {
     pub pages: Vec<PerksPage>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Eq)]
+// Where `upsert_perks_page` puts a page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PerksPageSlot {
+    // Overwrite this page, either because it has the same name or because it is the oldest one
+    Replace(isize),
+    New,
+}
+
+impl PerksPages {
+    /*
+        A page with the same name is replaced in place. Otherwise a new page is created while the
+        player owns fewer editable pages than `owned_page_count`, and once they are all in use the
+        least recently modified editable page is given up for it.
+    */
+    pub fn slot_for(&self, name: &str, owned_page_count: isize) -> PerksPageSlot {
+        let editable = self.pages.iter().filter(|page| page.is_editable);
+
+        if let Some(page) = editable.clone().find(|page| page.name == name) {
+            return PerksPageSlot::Replace(page.id);
+        }
+
+        if (editable.clone().count() as isize) < owned_page_count {
+            return PerksPageSlot::New;
+        }
+
+        editable
+            .min_by_key(|page| page.last_modified)
+            .map_or(PerksPageSlot::New, |page| PerksPageSlot::Replace(page.id))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct PerksPage {
-    pub auto_modified_selections: Vec<isize>,
+    pub auto_modified_selections: Vec<PerkId>,
     pub current: bool,
     pub id: isize,
     pub is_active: bool,
@@ -61,9 +117,9 @@ pub struct PerksPage {
     pub last_modified: isize,
     pub name: String,
     pub order: isize,
-    pub primary_style_id: isize,
-    pub selected_perk_ids: Vec<isize>,
-    pub sub_style_id: isize,
+    pub primary_style_id: PerkStyleId,
+    pub selected_perk_ids: Vec<PerkId>,
+    pub sub_style_id: PerkStyleId,
 }
 
 impl std::cmp::PartialEq for PerksPage {
@@ -88,9 +144,296 @@ impl Default for PerksPage {
             last_modified: 0,
             name: "".to_owned(),
             order: 0,
-            primary_style_id: 0,
+            primary_style_id: PerkStyleId(0),
             selected_perk_ids: Vec::new(),
-            sub_style_id: 0,
+            sub_style_id: PerkStyleId(0),
         }
     }
 }
+
+/*
+    Builds a page from its choices instead of a raw list of perk IDs. `build` checks the choices
+    against the styles and perks the client knows about, so an invalid page is reported with a reason
+    rather than rejected by the client. The perks are ordered the way the client expects them: the
+    keystone, the primary perks, the secondary perks and then the stat shards.
+*/
+#[derive(Debug, Clone)]
+pub struct PerksPageBuilder {
+    name: String,
+    primary_style_id: Option<PerkStyleId>,
+    keystone: Option<PerkId>,
+    primary_perks: Vec<PerkId>,
+    sub_style_id: Option<PerkStyleId>,
+    sub_perks: Vec<PerkId>,
+    stat_shards: Vec<PerkId>,
+}
+
+impl PerksPageBuilder {
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            primary_style_id: None,
+            keystone: None,
+            primary_perks: Vec::new(),
+            sub_style_id: None,
+            sub_perks: Vec::new(),
+            stat_shards: Vec::new(),
+        }
+    }
+
+    pub fn primary_style(mut self, style_id: PerkStyleId) -> Self {
+        self.primary_style_id = Some(style_id);
+        self
+    }
+
+    pub fn keystone(mut self, perk_id: PerkId) -> Self {
+        self.keystone = Some(perk_id);
+        self
+    }
+
+    // One perk from each of the primary style's regular slots, in any order.
+    pub fn primary_perks(mut self, perk_ids: [PerkId; 3]) -> Self {
+        self.primary_perks = perk_ids.to_vec();
+        self
+    }
+
+    pub fn secondary_style(mut self, style_id: PerkStyleId) -> Self {
+        self.sub_style_id = Some(style_id);
+        self
+    }
+
+    // Two perks from different regular slots of the secondary style, in any order.
+    pub fn secondary_perks(mut self, perk_ids: [PerkId; 2]) -> Self {
+        self.sub_perks = perk_ids.to_vec();
+        self
+    }
+
+    // Offense, flex and defense, in that order.
+    pub fn stat_shards(mut self, perk_ids: [PerkId; 3]) -> Self {
+        self.stat_shards = perk_ids.to_vec();
+        self
+    }
+
+    pub fn build(&self, styles: &[PerkStyle], perks: &[PerkData]) -> Result<PerksPage> {
+        let primary_style_id = self
+            .primary_style_id
+            .ok_or_else(|| invalid_page("no primary style was chosen"))?;
+        let sub_style_id = self
+            .sub_style_id
+            .ok_or_else(|| invalid_page("no secondary style was chosen"))?;
+        let keystone = self
+            .keystone
+            .ok_or_else(|| invalid_page("no keystone was chosen"))?;
+
+        let primary_style = find_style(styles, primary_style_id)?;
+        let sub_style = find_style(styles, sub_style_id)?;
+
+        if !primary_style.allowed_sub_styles.contains(&sub_style_id) {
+            return Err(invalid_page(format!(
+                "{} cannot be the secondary style of {}",
+                sub_style.name, primary_style.name
+            )));
+        }
+
+        if !slots_of(primary_style, PerkSlotType::KeyStone).any(|slot| slot.contains(&keystone)) {
+            return Err(invalid_page(format!(
+                "{} is not a {} keystone",
+                keystone, primary_style.name
+            )));
+        }
+
+        let mut selected_perk_ids = vec![keystone];
+
+        for slot in slots_of(primary_style, PerkSlotType::MixedRegularSplashable) {
+            let mut chosen = self.primary_perks.iter().filter(|id| slot.contains(id));
+
+            match (chosen.next(), chosen.next()) {
+                (Some(&perk_id), None) => selected_perk_ids.push(perk_id),
+                _ => {
+                    return Err(invalid_page(format!(
+                        "exactly one perk must be chosen from each {} slot",
+                        primary_style.name
+                    )));
+                }
+            }
+        }
+
+        if selected_perk_ids.len() != 1 + self.primary_perks.len() {
+            return Err(invalid_page(format!(
+                "the primary perks must all come from {}",
+                primary_style.name
+            )));
+        }
+
+        for slot in slots_of(sub_style, PerkSlotType::MixedRegularSplashable) {
+            let mut chosen = self.sub_perks.iter().filter(|id| slot.contains(id));
+
+            match (chosen.next(), chosen.next()) {
+                (Some(&perk_id), None) => selected_perk_ids.push(perk_id),
+                (None, _) => {}
+                _ => {
+                    return Err(invalid_page(format!(
+                        "the secondary perks must come from different {} slots",
+                        sub_style.name
+                    )));
+                }
+            }
+        }
+
+        if selected_perk_ids.len() != 1 + self.primary_perks.len() + self.sub_perks.len() {
+            return Err(invalid_page(format!(
+                "the secondary perks must be regular {} perks",
+                sub_style.name
+            )));
+        }
+
+        let stat_slots: Vec<&Vec<PerkId>> =
+            slots_of(primary_style, PerkSlotType::StatMod).collect();
+
+        if stat_slots.len() != self.stat_shards.len()
+            || !stat_slots
+                .iter()
+                .zip(&self.stat_shards)
+                .all(|(slot, shard)| slot.contains(shard))
+        {
+            return Err(invalid_page(
+                "the stat shards must be one offense, flex and defense shard",
+            ));
+        }
+
+        selected_perk_ids.extend(&self.stat_shards);
+
+        if let Some(unknown) = selected_perk_ids
+            .iter()
+            .find(|&&id| !perks.iter().any(|perk| perk.id == id))
+        {
+            return Err(invalid_page(format!("perk {} does not exist", unknown)));
+        }
+
+        Ok(PerksPage {
+            name: self.name.clone(),
+            primary_style_id,
+            selected_perk_ids,
+            sub_style_id,
+            ..PerksPage::default()
+        })
+    }
+}
+
+pub(crate) fn invalid_page<S: AsRef<str>>(reason: S) -> LcuDriverError {
+    LcuDriverError::InvalidPerksPage(reason.as_ref().to_string())
+}
+
+fn find_style(styles: &[PerkStyle], style_id: PerkStyleId) -> Result<&PerkStyle> {
+    styles
+        .iter()
+        .find(|style| style.id == style_id)
+        .ok_or_else(|| invalid_page(format!("style {} does not exist", style_id)))
+}
+
+fn slots_of(style: &PerkStyle, slot_type: PerkSlotType) -> impl Iterator<Item = &Vec<PerkId>> {
+    style
+        .slots
+        .iter()
+        .filter(move |slot| slot.type_field == slot_type)
+        .map(|slot| &slot.perks)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::endpoints::fixtures::{assert_round_trip, build_perks_page};
+    use crate::endpoints::ids::{PerkId, PerkStyleId};
+    use crate::endpoints::perks::{
+        PerksInventory, PerksPage, PerksPageBuilder, PerksPageSlot, PerksPages,
+    };
+    use crate::errors::LcuDriverError;
+
+    fn conqueror() -> PerksPageBuilder {
+        PerksPageBuilder::new("Jungle Conqueror")
+            .primary_style(PerkStyleId(8000))
+            .keystone(PerkId(8010))
+            .primary_perks([PerkId(8299), PerkId(9111), PerkId(9104)])
+            .secondary_style(PerkStyleId(8100))
+            .secondary_perks([PerkId(8135), PerkId(8143)])
+            .stat_shards([PerkId(5005), PerkId(5008), PerkId(5002)])
+    }
+
+    fn rejection(builder: PerksPageBuilder) -> String {
+        match build_perks_page(builder) {
+            Err(LcuDriverError::InvalidPerksPage(reason)) => reason,
+            other => panic!("Expected the page to be rejected, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_pages() {
+        let pages: Vec<PerksPage> = assert_round_trip("perks/pages.json");
+
+        assert_eq!(pages.len(), 3);
+        assert_eq!(pages.iter().filter(|p| p.current).count(), 1);
+        assert!(pages.iter().all(|p| p.selected_perk_ids.len() == 9));
+        assert!(!pages[2].is_editable);
+    }
+
+    #[test]
+    fn test_inventory() {
+        let inventory: PerksInventory = assert_round_trip("perks/inventory.json");
+
+        assert_eq!(inventory.owned_page_count, 7);
+    }
+
+    #[test]
+    fn test_page_slots() {
+        let pages = PerksPages {
+            pages: assert_round_trip("perks/pages.json"),
+        };
+
+        assert_eq!(
+            pages.slot_for("Arcane Comet", 2),
+            PerksPageSlot::Replace(1741227311)
+        );
+        assert_eq!(pages.slot_for("Lethality", 3), PerksPageSlot::New);
+        // The preset Domination page does not count towards the limit and is never replaced
+        assert_eq!(
+            pages.slot_for("Lethality", 2),
+            PerksPageSlot::Replace(1741227311)
+        );
+        assert_eq!(pages.slot_for("Domination", 3), PerksPageSlot::New);
+    }
+
+    #[test]
+    fn test_builder_orders_perks_by_slot() {
+        let pages: Vec<PerksPage> = assert_round_trip("perks/pages.json");
+
+        // Matches the first page in the fixture, which the client accepted
+        assert_eq!(build_perks_page(conqueror()).unwrap(), pages[0]);
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_choices() {
+        assert_eq!(
+            rejection(conqueror().keystone(PerkId(8112))),
+            "8112 is not a Precision keystone"
+        );
+        assert_eq!(
+            rejection(conqueror().primary_perks([PerkId(9111), PerkId(9101), PerkId(8299)])),
+            "exactly one perk must be chosen from each Precision slot"
+        );
+        assert_eq!(
+            rejection(conqueror().secondary_perks([PerkId(8134), PerkId(8135)])),
+            "the secondary perks must come from different Domination slots"
+        );
+        assert_eq!(
+            rejection(conqueror().secondary_perks([PerkId(8112), PerkId(8135)])),
+            "the secondary perks must be regular Domination perks"
+        );
+        assert_eq!(
+            rejection(conqueror().secondary_style(PerkStyleId(8000))),
+            "Precision cannot be the secondary style of Precision"
+        );
+        assert_eq!(
+            rejection(conqueror().stat_shards([PerkId(5002), PerkId(5008), PerkId(5005)])),
+            "the stat shards must be one offense, flex and defense shard"
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
 
+use crate::endpoints::ids::SummonerId;
 use crate::endpoints::EndpointInfo;
 
 const SUMMONER_URL: &str = "/lol-summoner/v1";
@@ -34,7 +35,7 @@ pub struct Summoner {
     pub profile_icon_id: isize,
     pub puuid: String,
     pub reroll_points: RerollPoints,
-    pub summoner_id: isize,
+    pub summoner_id: SummonerId,
     pub summoner_level: isize,
     pub unnamed: bool,
     pub xp_since_last_level: isize,
@@ -50,3 +51,18 @@ pub struct RerollPoints {
     pub points_cost_to_roll: isize,
     pub points_to_reroll: isize,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::endpoints::fixtures::assert_round_trip;
+    use crate::endpoints::ids::SummonerId;
+    use crate::endpoints::summoner::Summoner;
+
+    #[test]
+    fn test_current_summoner() {
+        let summoner: Summoner = assert_round_trip("summoner/current_summoner.json");
+
+        assert_eq!(summoner.summoner_id, SummonerId(2611938));
+        assert_eq!(summoner.reroll_points.max_rolls, 2);
+    }
+}
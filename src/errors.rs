@@ -4,7 +4,7 @@ use std::fmt::Formatter;
 use crate::convert_error;
 use crate::models::api_error::LcuApiError;
 
-#[derive(Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq)]
 pub enum LcuDriverError {
     FailedToFindLeagueProcess,
     FailedToReadLockfileToken,
@@ -12,6 +12,10 @@ pub enum LcuDriverError {
     FailedToReadResponse(String),
     FailedToReadCertificate,
     FailedToFindLutrisPrefix,
+    InvalidAssetPath(String),
+    InvalidClientVersion(String),
+    NoChampSelectActionInProgress,
+    InvalidPerksPage(String),
     ApiError(LcuApiError),
     Other(String),
 }
@@ -29,6 +33,18 @@ impl fmt::Display for LcuDriverError {
             }
             LcuDriverError::FailedToReadCertificate => "Failed to read riot certificate file",
             LcuDriverError::FailedToFindLutrisPrefix => "Failed to find lutris prefix",
+            LcuDriverError::InvalidAssetPath(path) => {
+                return write!(f, "Invalid game data asset path - {}", path);
+            }
+            LcuDriverError::InvalidClientVersion(version) => {
+                return write!(f, "Invalid client version - {:?}", version);
+            }
+            LcuDriverError::NoChampSelectActionInProgress => {
+                "No champ select action is in progress for the local player"
+            }
+            LcuDriverError::InvalidPerksPage(reason) => {
+                return write!(f, "Invalid rune page - {}", reason);
+            }
             LcuDriverError::ApiError(e) => return e.fmt(f),
             LcuDriverError::Other(message) => message,
         };
@@ -62,6 +78,8 @@ convert_error!(url::ParseError);
 convert_error!(http::uri::InvalidUri);
 convert_error!(http::Error);
 convert_error!(std::path::StripPrefixError);
+convert_error!(toml::de::Error);
+convert_error!(toml::ser::Error);
 
 #[macro_export]
 macro_rules! convert_error {
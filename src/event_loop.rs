@@ -0,0 +1,75 @@
+use serde::de::DeserializeOwned;
+use tokio::time::Instant;
+
+use crate::websocket::LcuEventType;
+use crate::{Initialized, LcuDriver, Result};
+
+// The part of a background helper that reacts to one resource, see `watch_uri`.
+pub(crate) trait UriHandler {
+    type Data: DeserializeOwned;
+
+    /*
+        React to an update of the resource, or read it from the driver when `data` is `None`. Returns
+        when to be called again without an update, if at all. Failures are handled here, since the
+        helper keeps going until the websocket closes.
+    */
+    async fn handle(
+        &mut self,
+        driver: &LcuDriver<Initialized>,
+        data: Option<Self::Data>,
+    ) -> Option<Instant>;
+
+    // The resource was deleted, e.g. champ select ended.
+    fn reset(&mut self) {}
+}
+
+/*
+    The loop shared by the background helpers. Subscribes to `uri`, lets the handler look at the current
+    state, since the resource may already exist when we start, and then passes it every update until the
+    websocket closes. Updates that do not deserialize into the handler's data are skipped.
+*/
+pub(crate) async fn watch_uri<H: UriHandler>(
+    driver: &LcuDriver<Initialized>,
+    uri: &str,
+    handler: &mut H,
+) -> Result<()> {
+    let mut websocket = driver.connect_websocket().await?;
+    websocket.subscribe_uri(uri).await?;
+
+    let mut recheck_at = handler.handle(driver, None).await;
+
+    loop {
+        let recheck = async {
+            match recheck_at {
+                Some(at) => tokio::time::sleep_until(at).await,
+                None => std::future::pending().await,
+            }
+        };
+
+        tokio::select! {
+            event = websocket.next_event() => {
+                let event = match event {
+                    Some(event) => event?,
+                    None => return Ok(()),
+                };
+
+                if event.uri != uri {
+                    continue;
+                }
+
+                if event.event_type == LcuEventType::Delete {
+                    handler.reset();
+                    recheck_at = None;
+                    continue;
+                }
+
+                if let Ok(data) = serde_json::from_value(event.data) {
+                    recheck_at = handler.handle(driver, Some(data)).await;
+                }
+            }
+            _ = recheck => {
+                recheck_at = handler.handle(driver, None).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::endpoints::EndpointInfo;
+use crate::errors::LcuDriverError;
+use crate::Result;
+
+/*
+    Hooks that run around every HTTP request and websocket message the driver makes. Interceptors
+    are registered on the `LcuDriverBuilder` and are called in the order they were added. Every hook
+    has a default no-op implementation so an interceptor only needs to implement what it cares about.
+*/
+pub trait Interceptor: Send + Sync {
+    /*
+        Called before a request is sent. The endpoint can be modified (e.g. to add a request ID
+        header). Returning an error aborts the request with that error, which is useful for fault
+        injection in tests.
+    */
+    fn on_request(&self, _endpoint: &mut EndpointInfo) -> Result<()> {
+        Ok(())
+    }
+
+    // Called once a response has been received from the client, including error responses.
+    fn on_response(&self, _endpoint: &EndpointInfo, _response: &ResponseInfo) {}
+
+    // Called when a request failed, either because it could not be sent or the API returned an error.
+    fn on_error(&self, _endpoint: &EndpointInfo, _error: &LcuDriverError, _elapsed: Duration) {}
+
+    /*
+        Called after a failed attempt. If any interceptor returns true the request is sent again,
+        unless it has already used up `LcuDriverBuilder::max_request_attempts`. `attempt` starts at 1
+        for the first failed attempt.
+    */
+    fn should_retry(
+        &self,
+        _endpoint: &EndpointInfo,
+        _error: &LcuDriverError,
+        _attempt: usize,
+    ) -> bool {
+        false
+    }
+
+    // Called before a message is sent over the websocket. Returning an error aborts the send.
+    fn on_websocket_send(&self, _message: &Message) -> Result<()> {
+        Ok(())
+    }
+
+    // Called for every message received over the websocket.
+    fn on_websocket_receive(&self, _message: &Message) {}
+}
+
+// Lets a shared interceptor be registered while a handle to it is kept, e.g. to read collected metrics.
+impl<T: Interceptor + ?Sized> Interceptor for Arc<T> {
+    fn on_request(&self, endpoint: &mut EndpointInfo) -> Result<()> {
+        (**self).on_request(endpoint)
+    }
+
+    fn on_response(&self, endpoint: &EndpointInfo, response: &ResponseInfo) {
+        (**self).on_response(endpoint, response)
+    }
+
+    fn on_error(&self, endpoint: &EndpointInfo, error: &LcuDriverError, elapsed: Duration) {
+        (**self).on_error(endpoint, error, elapsed)
+    }
+
+    fn should_retry(
+        &self,
+        endpoint: &EndpointInfo,
+        error: &LcuDriverError,
+        attempt: usize,
+    ) -> bool {
+        (**self).should_retry(endpoint, error, attempt)
+    }
+
+    fn on_websocket_send(&self, message: &Message) -> Result<()> {
+        (**self).on_websocket_send(message)
+    }
+
+    fn on_websocket_receive(&self, message: &Message) {
+        (**self).on_websocket_receive(message)
+    }
+}
+
+#[derive(Debug)]
+pub struct ResponseInfo<'a> {
+    pub status: StatusCode,
+    pub elapsed: Duration,
+    // Not available for streamed responses such as asset downloads.
+    pub body: Option<&'a str>,
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod driver_tests {
+    use std::num::NonZeroUsize;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use reqwest::Method;
+
+    use crate::endpoints::fixtures::load_fixture;
+    use crate::endpoints::EndpointInfo;
+    use crate::errors::LcuDriverError;
+    use crate::interceptor::Interceptor;
+    use crate::testing::{FakeTransport, MockResponse};
+    use crate::Result;
+
+    const SUMMONER_PATH: &str = "/lol-summoner/v1/current-summoner";
+
+    // Records the order it was called in and optionally aborts the request.
+    struct RequestHook {
+        name: &'static str,
+        calls: Arc<Mutex<Vec<&'static str>>>,
+        abort: bool,
+    }
+
+    impl Interceptor for RequestHook {
+        fn on_request(&self, _endpoint: &mut EndpointInfo) -> Result<()> {
+            self.calls.lock().unwrap().push(self.name);
+
+            if self.abort {
+                return Err(LcuDriverError::new("Aborted by interceptor"));
+            }
+
+            Ok(())
+        }
+    }
+
+    struct AlwaysRetry;
+
+    impl Interceptor for AlwaysRetry {
+        fn should_retry(
+            &self,
+            _endpoint: &EndpointInfo,
+            _error: &LcuDriverError,
+            _attempt: usize,
+        ) -> bool {
+            true
+        }
+    }
+
+    fn summoner_requests(transport: &FakeTransport) -> usize {
+        transport
+            .requests()
+            .iter()
+            .filter(|r| r.path == SUMMONER_PATH)
+            .count()
+    }
+
+    #[tokio::test]
+    async fn test_on_request_runs_in_registration_order() {
+        let transport = FakeTransport::new();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        transport.route(
+            Method::GET,
+            SUMMONER_PATH,
+            MockResponse::json(&load_fixture("summoner/current_summoner.json")),
+        );
+
+        let driver = transport
+            .driver_builder("./test_data/lockfile")
+            .interceptor(RequestHook {
+                name: "first",
+                calls: calls.clone(),
+                abort: false,
+            })
+            .interceptor(RequestHook {
+                name: "second",
+                calls: calls.clone(),
+                abort: false,
+            })
+            .connect()
+            .await
+            .unwrap();
+
+        driver.get_current_summoner().await.unwrap();
+
+        assert_eq!(*calls.lock().unwrap(), ["first", "second"]);
+    }
+
+    #[tokio::test]
+    async fn test_on_request_error_aborts_request() {
+        let transport = FakeTransport::new();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        let driver = transport
+            .driver_builder("./test_data/lockfile")
+            .interceptor(RequestHook {
+                name: "first",
+                calls: calls.clone(),
+                abort: true,
+            })
+            .interceptor(RequestHook {
+                name: "second",
+                calls: calls.clone(),
+                abort: false,
+            })
+            .interceptor(AlwaysRetry)
+            .connect()
+            .await
+            .unwrap();
+
+        assert!(driver.get_current_summoner().await.is_err());
+        assert_eq!(*calls.lock().unwrap(), ["first"]);
+        assert_eq!(summoner_requests(&transport), 0);
+    }
+
+    #[tokio::test]
+    async fn test_retries_until_request_succeeds() {
+        let transport = FakeTransport::new();
+
+        transport.route_sequence(
+            Method::GET,
+            SUMMONER_PATH,
+            vec![
+                MockResponse::error(500, "Internal error"),
+                MockResponse::error(503, "Unavailable"),
+                MockResponse::json(&load_fixture("summoner/current_summoner.json")),
+            ],
+        );
+
+        let driver = transport
+            .driver_builder("./test_data/lockfile")
+            .interceptor(AlwaysRetry)
+            .retry_backoff(Duration::from_millis(1))
+            .connect()
+            .await
+            .unwrap();
+
+        driver.get_current_summoner().await.unwrap();
+
+        assert_eq!(summoner_requests(&transport), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retries_stop_at_attempt_limit() {
+        let transport = FakeTransport::new();
+
+        transport.route(
+            Method::GET,
+            SUMMONER_PATH,
+            MockResponse::error(500, "Internal error"),
+        );
+
+        let driver = transport
+            .driver_builder("./test_data/lockfile")
+            .interceptor(AlwaysRetry)
+            .max_request_attempts(NonZeroUsize::new(2).unwrap())
+            .retry_backoff(Duration::from_millis(1))
+            .connect()
+            .await
+            .unwrap();
+
+        assert!(driver.get_current_summoner().await.is_err());
+        assert_eq!(summoner_requests(&transport), 2);
+    }
+}
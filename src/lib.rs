@@ -1,32 +1,70 @@
-use std::io::Cursor;
-use std::path::PathBuf;
-use std::str::FromStr;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use futures_util::StreamExt;
 use reqwest::header::{HeaderMap, HeaderValue};
-use reqwest::{Body, Certificate as ReqwestCertificate, Client, ClientBuilder, Method, Request};
-use rustls::{Certificate, ClientConfig, RootCertStore};
+use reqwest::Method;
 use serde::de::DeserializeOwned;
-use tokio::net::TcpStream;
-use tokio::sync::RwLock;
-use tokio_tungstenite::tungstenite::client::IntoClientRequest;
-use tokio_tungstenite::tungstenite::http::Response;
-use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
-
-use crate::endpoints::champ_select::{ChampSelectEndpoint, ChampSelectSession, MySelection};
-use crate::endpoints::gameflow::{GameFlowEndpoint, GameFlowSession};
-use crate::endpoints::perks::{PerksEndpoint, PerksInventory, PerksPage, PerksPages};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::{RwLock, Semaphore};
+
+use crate::builder::LcuDriverBuilder;
+use crate::catalog::GameDataCatalogs;
+use crate::endpoints::champ_select::{
+    Action, ActionUpdate, ChampSelectEndpoint, ChampSelectSession, ChampionAvailability,
+    ContractAction, MySelection, SkinCarouselSkin,
+};
+use crate::endpoints::game_data::{
+    normalize_asset_path, DownloadProgress, GameDataEndpoint, PerkData, PerkStyle,
+};
+use crate::endpoints::gameflow::{GameFlowEndpoint, GameFlowPhase, GameFlowSession};
+use crate::endpoints::ids::{ChampionId, QueueId, SummonerId};
+use crate::endpoints::lobby::{
+    CreateLobby, InvitationRequest, Lobby, LobbyEndpoint, LobbyInvitation, LobbyPosition,
+    PositionPreferences,
+};
+use crate::endpoints::matchmaking::{MatchmakingEndpoint, MatchmakingSearch, ReadyCheck};
+use crate::endpoints::patch::PatchEndpoint;
+use crate::endpoints::perks::{
+    PerksEndpoint, PerksInventory, PerksPage, PerksPageBuilder, PerksPageSlot, PerksPages,
+};
 use crate::endpoints::summoner::{Summoner, SummonerEndpoint};
 use crate::endpoints::EndpointInfo;
 use crate::errors::LcuDriverError;
+use crate::interceptor::ResponseInfo;
+use crate::logging::{log_debug, log_info, log_trace, log_warn};
 use crate::models::api_error::ApiError;
+use crate::models::asset_cache::AssetCache;
 use crate::models::lcu_process::LcuProcess;
 use crate::models::lockfile::Lockfile;
-
+use crate::models::response_cache::{CacheInvalidator, ResponseCache};
+use crate::models::single_flight::SingleFlight;
+use crate::perks_export::{export_pages, read_exported_pages, write_exported_pages, ExportFormat};
+use crate::transport::{EventTransport, HttpTransport, TransportRequest, TransportResponse};
+use crate::websocket::LcuWebSocket;
+
+pub mod auto_accept;
+pub mod builder;
+pub mod cassette;
+pub mod catalog;
+pub mod champ_select_automation;
+pub mod champ_select_tracker;
+#[cfg(feature = "codegen")]
+pub mod codegen;
 pub mod endpoints;
 pub mod errors;
+mod event_loop;
+pub mod interceptor;
+mod logging;
 pub mod models;
+pub mod perks_export;
+pub mod schema_drift;
+pub mod spell_policy;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod transport;
+pub mod websocket;
 
 pub type Result<T> = std::result::Result<T, errors::LcuDriverError>;
 
@@ -41,7 +79,6 @@ pub struct Initialized {}
 struct LcuDriverInner {
     lcu_process: LcuProcess,
     lockfile: Lockfile,
-    client: Client,
     default_req_headers: HeaderMap,
     api_base_url: url::Url,
     websocket_base_url: url::Url,
@@ -51,114 +88,41 @@ impl LcuDriverInner {
     async fn replace_inner(&mut self, new_inner: LcuDriverInner) {
         self.lcu_process = new_inner.lcu_process;
         self.lockfile = new_inner.lockfile;
-        self.client = new_inner.client;
+        self.default_req_headers = new_inner.default_req_headers;
         self.api_base_url = new_inner.api_base_url;
+        self.websocket_base_url = new_inner.websocket_base_url;
     }
 }
 
+enum ResponseBody {
+    Text(String),
+    Stream(TransportResponse),
+}
+
 pub struct LcuDriver<S> {
     inner: RwLock<LcuDriverInner>,
     _state: S,
-    rustls_config: Arc<ClientConfig>,
+    http_transport: Arc<dyn HttpTransport>,
+    event_transport: Arc<dyn EventTransport>,
+    builder: LcuDriverBuilder,
+    response_cache: Option<Arc<ResponseCache>>,
+    // Replaced on reconnect, since the websocket address changes with the client
+    cache_invalidator: std::sync::Mutex<Option<CacheInvalidator>>,
+    request_limiter: Option<Semaphore>,
+    single_flight: Option<SingleFlight>,
 }
 
 impl LcuDriver<Uninitialized> {
-    pub async fn connect() -> Result<LcuDriver<Initialized>> {
-        let cert_raw = include_bytes!("../certs/riotgames.pem");
-        let reqwest_cert = ReqwestCertificate::from_pem(cert_raw)?;
-
-        let mut cert_raw_cursor = Cursor::new(cert_raw);
-        let rustls_cert_raw = rustls_pemfile::read_one(&mut cert_raw_cursor)?;
-
-        let rustls_cert_raw_bytes = match rustls_cert_raw {
-            Some(rustls_pemfile::Item::X509Certificate(bytes)) => bytes,
-            _ => {
-                eprintln!("Did not receive a valid X509Certificate.");
-                return Err(LcuDriverError::FailedToReadCertificate);
-            }
-        };
-
-        let rustls_cert = Certificate(rustls_cert_raw_bytes);
-
-        let mut root_store = RootCertStore::empty();
-        root_store.add(&rustls_cert).map_err(|e| {
-            eprintln!("{}", e);
-
-            LcuDriverError::FailedToReadCertificate
-        })?;
-
-        let rustls_config = ClientConfig::builder()
-            .with_safe_defaults()
-            .with_root_certificates(root_store)
-            .with_no_client_auth();
-
-        let lcu_process = LcuProcess::locate().await?;
-
-        let league_install_dir = lcu_process.install_directory();
-
-        let lockfile = Lockfile::load(league_install_dir.join("lockfile")).await?;
-
-        let mut headers = HeaderMap::with_capacity(2);
-        headers.insert("Accept", HeaderValue::from_static("*/*"));
-        headers.insert(
-            "Authorization",
-            HeaderValue::from_str(&format!("Basic {}", lockfile.token))?,
-        );
-
-        let client = ClientBuilder::new()
-            .default_headers(headers.clone())
-            .add_root_certificate(reqwest_cert)
-            .connect_timeout(Duration::from_secs(5))
-            .timeout(Duration::from_secs(5))
-            .build()?;
-
-        let api_base_url = url::Url::parse(&format!("https://127.0.0.1:{}", lockfile.port))?;
-
-        let websocket_base_url = url::Url::parse(&format!("wss://localhost:{}/", lockfile.port))?;
-
-        let inner_instance = LcuDriverInner {
-            lcu_process,
-            lockfile,
-            client,
-            default_req_headers: headers,
-            api_base_url,
-            websocket_base_url,
-        };
-
-        Ok(LcuDriver {
-            inner: RwLock::new(inner_instance),
-            rustls_config: Arc::new(rustls_config),
-            _state: Initialized {},
-        })
+    pub fn builder() -> LcuDriverBuilder {
+        LcuDriverBuilder::new()
     }
 
-    async fn connect_wait_no_reconnect() -> LcuDriver<Initialized> {
-        loop {
-            if let Ok(lcu_driver) = LcuDriver::connect().await {
-                //Check that we can actually connect to the client
-                if lcu_driver.get_current_summoner().await.is_ok() {
-                    return lcu_driver;
-                }
-            }
-
-            tokio::time::sleep(Duration::from_secs(1)).await;
-        }
+    pub async fn connect() -> Result<LcuDriver<Initialized>> {
+        LcuDriverBuilder::new().connect().await
     }
 
     pub async fn connect_wait() -> Arc<LcuDriver<Initialized>> {
-        loop {
-            if let Ok(lcu_driver) = LcuDriver::connect().await {
-                //Check that we can actually connect to the client
-                if lcu_driver.get_current_summoner().await.is_ok() {
-                    let pointer = Arc::new(lcu_driver);
-                    LcuDriver::start_lockfile_watching(pointer.clone());
-
-                    return pointer;
-                }
-            }
-
-            tokio::time::sleep(Duration::from_secs(1)).await;
-        }
+        LcuDriverBuilder::new().connect_wait().await
     }
 }
 
@@ -179,14 +143,9 @@ impl LcuDriver<Initialized> {
                 {
                     drop(current_inner);
 
-                    //hold the lock preventing any api calls from running
-                    let mut current_lcu_driver = lcu_driver.inner.write().await;
-
-                    let new_lcu_driver = LcuDriver::connect_wait_no_reconnect().await;
+                    log_info!("Lockfile changed, reconnecting to the League client");
 
-                    current_lcu_driver
-                        .replace_inner(new_lcu_driver.into_inner())
-                        .await;
+                    lcu_driver.reconnect().await;
                 }
 
                 tokio::time::sleep(Duration::from_secs(1)).await;
@@ -194,62 +153,64 @@ impl LcuDriver<Initialized> {
         });
     }
 
-    fn into_inner(self) -> LcuDriverInner {
-        self.inner.into_inner()
-    }
-
-    async fn format_url(&self, url: &str) -> Result<url::Url> {
-        let inner = self.inner.read().await;
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "lcu_reconnect", skip_all)
+    )]
+    async fn reconnect(&self) {
+        //hold the lock preventing any api calls from running
+        let mut current_lcu_driver = self.inner.write().await;
+
+        let new_lcu_driver = self.builder.connect_wait_no_reconnect().await;
+
+        current_lcu_driver
+            .replace_inner(new_lcu_driver.into_inner())
+            .await;
+
+        // Responses cached from the previous client session can no longer be trusted
+        if let Some(cache) = &self.response_cache {
+            cache.clear();
+
+            *self
+                .cache_invalidator
+                .lock()
+                .expect("Cache invalidator lock poisoned") = Some(CacheInvalidator::spawn(
+                cache.clone(),
+                self.event_transport.clone(),
+                current_lcu_driver.websocket_base_url.clone(),
+                current_lcu_driver.default_req_headers.clone(),
+            ));
+        }
 
-        Ok(inner.api_base_url.join(url)?)
+        log_debug!(port = current_lcu_driver.lockfile.port, "Reloaded lockfile");
     }
 
-    pub async fn connect_websocket(&self) -> Result<()> {
-        let (ws_stream, e) = self.connect_websocket_with_certs().await?;
-
-        Ok(())
+    fn into_inner(self) -> LcuDriverInner {
+        self.inner.into_inner()
     }
 
-    async fn connect_websocket_with_certs(
-        &self,
-    ) -> Result<(WebSocketStream<MaybeTlsStream<TcpStream>>, Response<()>)> {
-        let inner = self.inner.read().await;
-
-        let connector = tokio_tungstenite::Connector::Rustls(self.rustls_config.clone());
-
-        let uri = http::Uri::from_str(inner.websocket_base_url.as_str())?;
-
-        let mut ws_req = http::Request::builder().uri(&uri).body(())?;
-
-        *ws_req.headers_mut() = inner.default_req_headers.clone();
-
-        let request = ws_req.into_client_request()?;
-
-        let port = request
-            .uri()
-            .port_u16()
-            .or_else(|| match request.uri().scheme_str() {
-                Some("wss") => Some(443),
-                Some("ws") => Some(80),
-                _ => None,
-            })
-            .expect("Failed to read websocket port");
-
-        let domain = request
-            .uri()
-            .host()
-            .map(|d| d.to_string())
-            .expect("Failed to read websocket domain");
-
-        let addr = format!("{}:{}", domain, port);
-
-        let socket = tokio::net::TcpStream::connect(addr).await?;
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "lcu_websocket_connect", skip_all, err)
+    )]
+    pub async fn connect_websocket(&self) -> Result<LcuWebSocket> {
+        let channel = {
+            let inner = self.inner.read().await;
+
+            self.event_transport
+                .connect(
+                    inner.websocket_base_url.clone(),
+                    inner.default_req_headers.clone(),
+                )
+                .await?
+        };
 
-        let websocket =
-            tokio_tungstenite::client_async_tls_with_config(request, socket, None, Some(connector))
-                .await?;
+        log_debug!("Connected to the League client websocket");
 
-        Ok(websocket)
+        Ok(LcuWebSocket::new(
+            channel,
+            self.builder.interceptors.clone(),
+        ))
     }
 
     pub async fn get_current_summoner(&self) -> Result<Summoner> {
@@ -267,6 +228,11 @@ impl LcuDriver<Initialized> {
             .await
     }
 
+    pub async fn get_gameflow_phase(&self) -> Result<GameFlowPhase> {
+        self.get_and_deserialize_endpoint(GameFlowEndpoint::Phase.info())
+            .await
+    }
+
     pub async fn get_perks_inventory(&self) -> Result<PerksInventory> {
         self.get_and_deserialize_endpoint(PerksEndpoint::Inventory.info())
             .await
@@ -290,12 +256,70 @@ impl LcuDriver<Initialized> {
     }
 
     pub async fn delete_perks_page(&self, page_id: isize) -> Result<()> {
-        self.get_endpoint(PerksEndpoint::PagesId(Method::DELETE, page_id).info())
+        self.get_endpoint(PerksEndpoint::PagesId(Method::DELETE, page_id, None).info())
+            .await?;
+
+        Ok(())
+    }
+
+    // Overwrite the page with the same `id`.
+    pub async fn update_perks_page(&self, perks_page: &PerksPage) -> Result<()> {
+        let body = serde_json::to_string(perks_page)?;
+
+        self.get_endpoint(PerksEndpoint::PagesId(Method::PUT, perks_page.id, Some(body)).info())
             .await?;
 
         Ok(())
     }
 
+    pub async fn set_current_perks_page(&self, page_id: isize) -> Result<()> {
+        self.get_endpoint(
+            PerksEndpoint::CurrentPage(Method::PUT, Some(page_id.to_string())).info(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /*
+        Save a page by name without running into the owned page limit, see `PerksPages::slot_for`
+        for which page it ends up replacing.
+    */
+    pub async fn upsert_perks_page(&self, perks_page: &PerksPage) -> Result<()> {
+        let (pages, inventory) =
+            tokio::try_join!(self.get_perks_pages(), self.get_perks_inventory())?;
+
+        match pages.slot_for(&perks_page.name, inventory.owned_page_count) {
+            PerksPageSlot::Replace(page_id) => {
+                log_debug!(page_id, name = %perks_page.name, "Replacing rune page");
+
+                self.update_perks_page(&PerksPage {
+                    id: page_id,
+                    ..perks_page.clone()
+                })
+                .await
+            }
+            PerksPageSlot::New => self.set_perks_page(perks_page).await,
+        }
+    }
+
+    pub async fn get_perk_styles(&self) -> Result<Vec<PerkStyle>> {
+        self.get_and_deserialize_endpoint(PerksEndpoint::Styles.info())
+            .await
+    }
+
+    pub async fn get_perks(&self) -> Result<Vec<PerkData>> {
+        self.get_and_deserialize_endpoint(PerksEndpoint::Perks.info())
+            .await
+    }
+
+    // Validate the builder against the client's current styles and perks.
+    pub async fn build_perks_page(&self, builder: &PerksPageBuilder) -> Result<PerksPage> {
+        let (styles, perks) = tokio::try_join!(self.get_perk_styles(), self.get_perks())?;
+
+        builder.build(&styles, &perks)
+    }
+
     pub async fn set_session_my_selection(&self, my_selection: &MySelection) -> Result<()> {
         let my_selection = serde_json::to_string(my_selection)?;
 
@@ -305,52 +329,612 @@ impl LcuDriver<Initialized> {
         Ok(())
     }
 
-    pub async fn get_endpoint(&self, endpoint_info: EndpointInfo) -> Result<String> {
-        let inner = self.inner.read().await;
+    pub async fn update_champ_select_action(
+        &self,
+        action_id: isize,
+        update: &ActionUpdate,
+    ) -> Result<()> {
+        let update = serde_json::to_string(update)?;
 
-        let mut req = Request::new(
-            endpoint_info.method,
-            self.format_url(&endpoint_info.url)
-                .await
-                .expect("Invalid URL"),
-        );
+        self.get_endpoint(ChampSelectEndpoint::SessionAction(action_id, &update).info())
+            .await?;
 
-        if let Some(headers) = endpoint_info.headers {
-            for (k, v) in headers {
-                if let Some(k) = k {
-                    req.headers_mut().insert(k, v);
-                }
+        Ok(())
+    }
+
+    pub async fn complete_champ_select_action(&self, action_id: isize) -> Result<()> {
+        self.get_endpoint(ChampSelectEndpoint::CompleteSessionAction(action_id).info())
+            .await?;
+
+        Ok(())
+    }
+
+    // The local player's in-progress pick or ban, `None` while it is someone else's turn.
+    pub async fn get_current_champ_select_action(&self) -> Result<Option<Action>> {
+        let session = self.get_champ_select_session().await?;
+
+        Ok(session.current_action().cloned())
+    }
+
+    // Hover a champion for the local player's current pick or ban.
+    pub async fn hover_champion(&self, champion_id: ChampionId) -> Result<Action> {
+        self.act_on_current_action(ActionUpdate::hover(champion_id))
+            .await
+    }
+
+    // Pick or ban a champion and lock it in.
+    pub async fn lock_in_champion(&self, champion_id: ChampionId) -> Result<Action> {
+        self.act_on_current_action(ActionUpdate::lock_in(champion_id))
+            .await
+    }
+
+    async fn act_on_current_action(&self, update: ActionUpdate) -> Result<Action> {
+        let action = self
+            .get_current_champ_select_action()
+            .await?
+            .ok_or(LcuDriverError::NoChampSelectActionInProgress)?;
+
+        self.update_champ_select_action(action.id, &update).await?;
+
+        Ok(action)
+    }
+
+    pub async fn champ_select_trade(&self, trade_id: isize, action: ContractAction) -> Result<()> {
+        self.get_endpoint(ChampSelectEndpoint::Trade(trade_id, action).info())
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn pick_order_swap(&self, swap_id: isize, action: ContractAction) -> Result<()> {
+        self.get_endpoint(ChampSelectEndpoint::PickOrderSwap(swap_id, action).info())
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn position_swap(&self, swap_id: isize, action: ContractAction) -> Result<()> {
+        self.get_endpoint(ChampSelectEndpoint::PositionSwap(swap_id, action).info())
+            .await?;
+
+        Ok(())
+    }
+
+    // Take a champion from the ARAM bench, see `ChampSelectSession::is_on_bench`.
+    pub async fn swap_with_bench(&self, champion_id: ChampionId) -> Result<()> {
+        self.get_endpoint(ChampSelectEndpoint::BenchSwap(champion_id).info())
+            .await?;
+
+        Ok(())
+    }
+
+    // Reroll the local player's ARAM champion, spending one of `rerolls_remaining`.
+    pub async fn reroll_champion(&self) -> Result<()> {
+        self.get_endpoint(ChampSelectEndpoint::Reroll.info())
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_pickable_champion_ids(&self) -> Result<Vec<ChampionId>> {
+        self.get_and_deserialize_endpoint(ChampSelectEndpoint::PickableChampionIds.info())
+            .await
+    }
+
+    pub async fn get_bannable_champion_ids(&self) -> Result<Vec<ChampionId>> {
+        self.get_and_deserialize_endpoint(ChampSelectEndpoint::BannableChampionIds.info())
+            .await
+    }
+
+    pub async fn get_disabled_champion_ids(&self) -> Result<Vec<ChampionId>> {
+        self.get_and_deserialize_endpoint(ChampSelectEndpoint::DisabledChampionIds.info())
+            .await
+    }
+
+    pub async fn get_skin_carousel_skins(&self) -> Result<Vec<SkinCarouselSkin>> {
+        self.get_and_deserialize_endpoint(ChampSelectEndpoint::SkinCarouselSkins.info())
+            .await
+    }
+
+    // Fetch the champion lists and the session together, see `ChampionAvailability::is_pickable`.
+    pub async fn get_champion_availability(&self) -> Result<ChampionAvailability> {
+        let (session, pickable_champion_ids, bannable_champion_ids, disabled_champion_ids) = tokio::try_join!(
+            self.get_champ_select_session(),
+            self.get_pickable_champion_ids(),
+            self.get_bannable_champion_ids(),
+            self.get_disabled_champion_ids(),
+        )?;
+
+        Ok(ChampionAvailability {
+            session,
+            pickable_champion_ids,
+            bannable_champion_ids,
+            disabled_champion_ids,
+        })
+    }
+
+    pub async fn create_lobby(&self, queue_id: QueueId) -> Result<Lobby> {
+        let body = serde_json::to_string(&CreateLobby { queue_id })?;
+
+        self.get_and_deserialize_endpoint(LobbyEndpoint::CreateLobby(&body).info())
+            .await
+    }
+
+    pub async fn get_lobby(&self) -> Result<Lobby> {
+        self.get_and_deserialize_endpoint(LobbyEndpoint::Lobby.info())
+            .await
+    }
+
+    pub async fn leave_lobby(&self) -> Result<()> {
+        self.get_endpoint(LobbyEndpoint::LeaveLobby.info()).await?;
+
+        Ok(())
+    }
+
+    pub async fn set_position_preferences(
+        &self,
+        first_preference: LobbyPosition,
+        second_preference: LobbyPosition,
+    ) -> Result<()> {
+        let body = serde_json::to_string(&PositionPreferences {
+            first_preference,
+            second_preference,
+        })?;
+
+        self.get_endpoint(LobbyEndpoint::PositionPreferences(&body).info())
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_lobby_invitations(&self) -> Result<Vec<LobbyInvitation>> {
+        self.get_and_deserialize_endpoint(LobbyEndpoint::Invitations.info())
+            .await
+    }
+
+    pub async fn invite_to_lobby(&self, summoner_ids: &[SummonerId]) -> Result<()> {
+        let invitations: Vec<InvitationRequest> = summoner_ids
+            .iter()
+            .map(|&to_summoner_id| InvitationRequest { to_summoner_id })
+            .collect();
+
+        let body = serde_json::to_string(&invitations)?;
+
+        self.get_endpoint(LobbyEndpoint::SendInvitations(&body).info())
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn kick_lobby_member(&self, summoner_id: SummonerId) -> Result<()> {
+        self.get_endpoint(LobbyEndpoint::KickMember(summoner_id).info())
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn promote_lobby_member(&self, summoner_id: SummonerId) -> Result<()> {
+        self.get_endpoint(LobbyEndpoint::PromoteMember(summoner_id).info())
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn start_matchmaking_search(&self) -> Result<()> {
+        self.get_endpoint(LobbyEndpoint::StartSearch.info()).await?;
+
+        Ok(())
+    }
+
+    pub async fn cancel_matchmaking_search(&self) -> Result<()> {
+        self.get_endpoint(LobbyEndpoint::CancelSearch.info())
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_matchmaking_search(&self) -> Result<MatchmakingSearch> {
+        self.get_and_deserialize_endpoint(MatchmakingEndpoint::Search.info())
+            .await
+    }
+
+    pub async fn get_ready_check(&self) -> Result<ReadyCheck> {
+        self.get_and_deserialize_endpoint(MatchmakingEndpoint::ReadyCheck.info())
+            .await
+    }
+
+    pub async fn accept_ready_check(&self) -> Result<()> {
+        self.get_endpoint(MatchmakingEndpoint::AcceptReadyCheck.info())
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn decline_ready_check(&self) -> Result<()> {
+        self.get_endpoint(MatchmakingEndpoint::DeclineReadyCheck.info())
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_game_version(&self) -> Result<String> {
+        self.get_and_deserialize_endpoint(PatchEndpoint::GameVersion.info())
+            .await
+    }
+
+    // Load the champion, summoner spell and perk catalogs, see `CatalogCache` to keep them per version.
+    pub async fn load_game_data_catalogs(&self) -> Result<GameDataCatalogs> {
+        let client_version = self.get_game_version().await?;
+
+        GameDataCatalogs::load(self, client_version).await
+    }
+
+    /*
+        Write every editable rune page to its own file in `dir`, returning the paths of the files
+        written. Pages that cannot be exported are skipped, see `export_pages`.
+    */
+    pub async fn export_perks_pages(
+        &self,
+        dir: &Path,
+        format: ExportFormat,
+    ) -> Result<Vec<PathBuf>> {
+        let (pages, catalogs) =
+            tokio::try_join!(self.get_perks_pages(), self.load_game_data_catalogs())?;
+
+        let exported = export_pages(&pages.pages, &catalogs);
+
+        write_exported_pages(dir, &exported, format).await
+    }
+
+    /*
+        Save every page exported to `dir`, replacing pages with the same name. All of the pages are
+        validated before any are saved, so one bad file leaves the client's pages untouched.
+    */
+    pub async fn import_perks_pages(&self, dir: &Path) -> Result<Vec<PerksPage>> {
+        let exported = read_exported_pages(dir).await?;
+        let (catalogs, styles, perks) = tokio::try_join!(
+            self.load_game_data_catalogs(),
+            self.get_perk_styles(),
+            self.get_perks()
+        )?;
+
+        let pages = exported
+            .iter()
+            .map(|page| page.to_builder(&catalogs)?.build(&styles, &perks))
+            .collect::<Result<Vec<_>>>()?;
+
+        for page in &pages {
+            self.upsert_perks_page(page).await?;
+        }
+
+        Ok(pages)
+    }
+
+    /*
+        Stream a game data asset into the given writer without buffering it in memory. The progress
+        callback is invoked after every chunk that is written. Returns the total number of bytes
+        written.
+    */
+    pub async fn download_asset<W, F>(
+        &self,
+        asset_path: &str,
+        writer: &mut W,
+        mut progress: F,
+    ) -> Result<u64>
+    where
+        W: AsyncWrite + Unpin,
+        F: FnMut(DownloadProgress),
+    {
+        if normalize_asset_path(asset_path).is_none() {
+            return Err(LcuDriverError::InvalidAssetPath(asset_path.to_owned()));
+        }
+
+        let mut res = match self
+            .send_request(GameDataEndpoint::Asset(asset_path).info(), true)
+            .await?
+        {
+            ResponseBody::Stream(res) => res,
+            ResponseBody::Text(_) => unreachable!("Streamed requests never buffer the body"),
+        };
+
+        let total = res.content_length;
+        let mut downloaded = 0;
+
+        while let Some(chunk) = res.body.next().await.transpose()? {
+            writer.write_all(&chunk).await?;
+
+            downloaded += chunk.len() as u64;
+
+            progress(DownloadProgress { downloaded, total });
+        }
+
+        writer.flush().await?;
+
+        Ok(downloaded)
+    }
+
+    /*
+        Download a game data asset to the given file path. The asset is first written to a temporary
+        file next to the destination so that an interrupted download never leaves a partial file behind.
+    */
+    pub async fn download_asset_to_file<P, F>(
+        &self,
+        asset_path: &str,
+        file_path: P,
+        progress: F,
+    ) -> Result<u64>
+    where
+        P: AsRef<Path>,
+        F: FnMut(DownloadProgress),
+    {
+        let file_path = file_path.as_ref();
+
+        if let Some(parent) = file_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let mut tmp_path = file_path.as_os_str().to_owned();
+        tmp_path.push(".part");
+        let tmp_path = PathBuf::from(tmp_path);
+
+        let mut file = tokio::fs::File::create(&tmp_path).await?;
+
+        match self.download_asset(asset_path, &mut file, progress).await {
+            Ok(downloaded) => {
+                drop(file);
+                tokio::fs::rename(&tmp_path, file_path).await?;
+
+                Ok(downloaded)
+            }
+            Err(e) => {
+                drop(file);
+                let _ = tokio::fs::remove_file(&tmp_path).await;
+
+                Err(e)
             }
         }
+    }
 
-        if let Some(body) = endpoint_info.body {
-            req.headers_mut()
-                .insert("Content-Type", HeaderValue::from_static("application/json"));
-            *req.body_mut() = Some(Body::from(body))
+    /*
+        Return the local path of a game data asset, downloading it into the cache first if it has
+        not already been downloaded for the current client version.
+    */
+    pub async fn get_cached_asset<F>(
+        &self,
+        cache: &AssetCache,
+        asset_path: &str,
+        progress: F,
+    ) -> Result<PathBuf>
+    where
+        F: FnMut(DownloadProgress),
+    {
+        let client_version = self.get_game_version().await?;
+
+        if let Some(path) = cache.get(&client_version, asset_path).await? {
+            return Ok(path);
         }
 
-        let res = inner
-            .client
-            .execute(req)
-            .await
-            .map_err(|e| LcuDriverError::FailedToSendRequest(e.to_string()))?;
+        let staging_path = cache.staging_path(asset_path)?;
 
-        let status = res.status();
+        self.download_asset_to_file(asset_path, &staging_path, progress)
+            .await?;
 
-        let res_text = res
-            .text()
+        cache
+            .insert(&client_version, asset_path, &staging_path)
             .await
-            .map_err(|e| LcuDriverError::FailedToReadResponse(e.to_string()))?;
+    }
+
+    pub async fn get_endpoint(&self, endpoint_info: EndpointInfo) -> Result<String> {
+        let cache = self
+            .response_cache
+            .as_ref()
+            .filter(|_| endpoint_info.method == Method::GET && endpoint_info.body.is_none())
+            .filter(|cache| cache.is_cacheable(&endpoint_info.url));
+
+        if let Some(body) = cache.and_then(|cache| cache.get(&endpoint_info.url)) {
+            log_trace!(path = %endpoint_info.url, "Response cache hit");
+
+            return Ok(body);
+        }
+
+        let url = endpoint_info.url.clone();
+        let is_get = endpoint_info.method == Method::GET;
+        let generation = cache.map(|cache| cache.generation(&url));
+
+        let single_flight = self
+            .single_flight
+            .as_ref()
+            .filter(|_| is_get && endpoint_info.body.is_none() && endpoint_info.headers.is_none());
+
+        let body = match single_flight {
+            Some(single_flight) => {
+                single_flight
+                    .run(&url, || self.send_request_text(endpoint_info))
+                    .await?
+            }
+            None => self.send_request_text(endpoint_info).await?,
+        };
+
+        if let (Some(cache), Some(generation)) = (cache, generation) {
+            cache.insert(&url, &body, generation);
+        } else if let Some(cache) = self.response_cache.as_ref().filter(|_| !is_get) {
+            // The driver modified a resource, so anything cached for it is now stale
+            cache.invalidate(&url);
+        }
+
+        Ok(body)
+    }
+
+    async fn send_request_text(&self, endpoint_info: EndpointInfo) -> Result<String> {
+        match self.send_request(endpoint_info, false).await? {
+            ResponseBody::Text(text) => Ok(text),
+            ResponseBody::Stream(res) => res.text().await,
+        }
+    }
+
+    /*
+        Send a request through the interceptor chain, retrying it with a backoff for as long as an
+        interceptor asks to and the attempt limit allows. Unless the response is streamed, the body
+        is read as part of the attempt so that interceptors get to see it.
+    */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "lcu_http",
+            skip_all,
+            fields(method = %endpoint_info.method, path = %endpoint_info.url)
+        )
+    )]
+    async fn send_request(
+        &self,
+        mut endpoint_info: EndpointInfo,
+        stream: bool,
+    ) -> Result<ResponseBody> {
+        for interceptor in self.builder.interceptors.iter() {
+            interceptor.on_request(&mut endpoint_info)?;
+        }
+
+        let mut attempt = 0;
 
-        if !status.is_success() {
-            let err = serde_json::from_str::<ApiError>(&res_text)?;
+        loop {
+            attempt += 1;
+
+            // For streamed responses the permit is released once the response headers are received
+            let permit = match &self.request_limiter {
+                Some(limiter) => Some(
+                    limiter
+                        .acquire()
+                        .await
+                        .expect("Request limiter is never closed"),
+                ),
+                None => None,
+            };
+
+            let start = Instant::now();
+
+            let res = match self.execute_request(endpoint_info.clone()).await {
+                Ok(res) => self.read_response(&endpoint_info, res, stream, start).await,
+                Err(e) => Err(e),
+            };
+
+            // Released before backing off so a failing request does not hold up other callers
+            drop(permit);
+
+            match res {
+                Ok(body) => return Ok(body),
+                Err(e) => {
+                    let elapsed = start.elapsed();
+
+                    log_warn!(
+                        error = %e,
+                        latency_ms = elapsed.as_millis() as u64,
+                        attempt,
+                        "Request failed"
+                    );
+
+                    for interceptor in self.builder.interceptors.iter() {
+                        interceptor.on_error(&endpoint_info, &e, elapsed);
+                    }
+
+                    let retry = attempt < self.builder.request_attempts_limit()
+                        && self
+                            .builder
+                            .interceptors
+                            .iter()
+                            .any(|i| i.should_retry(&endpoint_info, &e, attempt));
+
+                    if !retry {
+                        return Err(e);
+                    }
+
+                    let delay = self.builder.retry_delay(attempt);
+
+                    log_debug!(
+                        attempt,
+                        delay_ms = delay.as_millis() as u64,
+                        "Retrying request"
+                    );
+
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        }
+    }
 
-            Err(err.into())
+    // Pass the response to the interceptors and turn error statuses into an `ApiError`.
+    async fn read_response(
+        &self,
+        endpoint_info: &EndpointInfo,
+        res: TransportResponse,
+        stream: bool,
+        start: Instant,
+    ) -> Result<ResponseBody> {
+        let status = res.status;
+
+        let body = if stream && status.is_success() {
+            ResponseBody::Stream(res)
         } else {
-            Ok(res_text)
+            ResponseBody::Text(res.text().await?)
+        };
+
+        let response_info = ResponseInfo {
+            status,
+            elapsed: start.elapsed(),
+            body: match &body {
+                ResponseBody::Text(text) => Some(text),
+                ResponseBody::Stream(_) => None,
+            },
+        };
+
+        log_debug!(
+            status = status.as_u16(),
+            latency_ms = response_info.elapsed.as_millis() as u64,
+            "Received response"
+        );
+
+        for interceptor in self.builder.interceptors.iter() {
+            interceptor.on_response(endpoint_info, &response_info);
+        }
+
+        match body {
+            ResponseBody::Text(text) if !status.is_success() => {
+                let err = serde_json::from_str::<ApiError>(&text)?;
+
+                Err(err.into())
+            }
+            body => Ok(body),
         }
     }
 
+    async fn execute_request(&self, endpoint_info: EndpointInfo) -> Result<TransportResponse> {
+        let request = {
+            let inner = self.inner.read().await;
+
+            let mut headers = inner.default_req_headers.clone();
+
+            if let Some(endpoint_headers) = endpoint_info.headers {
+                for (k, v) in endpoint_headers {
+                    if let Some(k) = k {
+                        headers.insert(k, v);
+                    }
+                }
+            }
+
+            if endpoint_info.body.is_some() {
+                headers.insert("Content-Type", HeaderValue::from_static("application/json"));
+            }
+
+            TransportRequest {
+                method: endpoint_info.method,
+                url: inner.api_base_url.join(&endpoint_info.url)?,
+                headers,
+                body: endpoint_info.body,
+            }
+        };
+
+        self.http_transport.send(request).await
+    }
+
     pub async fn get_and_deserialize_endpoint<T: DeserializeOwned>(
         &self,
         endpoint_info: EndpointInfo,
@@ -0,0 +1,42 @@
+/*
+    Thin wrappers around the `tracing` macros so that the rest of the crate can emit events without
+    sprinkling `#[cfg(feature = "tracing")]` everywhere. When the feature is disabled the macros
+    expand to nothing.
+*/
+
+macro_rules! log_trace {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::trace!($($arg)*);
+    };
+}
+
+macro_rules! log_debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+    };
+}
+
+macro_rules! log_info {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::info!($($arg)*);
+    };
+}
+
+macro_rules! log_warn {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::warn!($($arg)*);
+    };
+}
+
+macro_rules! log_error {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::error!($($arg)*);
+    };
+}
+
+pub(crate) use {log_debug, log_error, log_info, log_trace, log_warn};
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::errors::LcuDriverError;
 
-#[derive(Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq)]
 pub enum LcuApiError {
     NoActiveDelegate,
     UnknownError(String),
@@ -0,0 +1,324 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use ring::digest::{Context, SHA256};
+use tokio::io::AsyncReadExt;
+
+use crate::endpoints::game_data::normalize_asset_path;
+use crate::errors::LcuDriverError;
+use crate::Result;
+
+const BLOBS_DIR: &str = "blobs";
+const VERSIONS_DIR: &str = "versions";
+const STAGING_DIR: &str = "staging";
+
+// Keeps staging files of concurrent downloads in the same process apart.
+static STAGING_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/*
+    A local on-disk cache of game data assets. Asset contents are stored once under the SHA-256 hash
+    of their bytes, and each client version has an index that maps asset paths to those blobs. A patch
+    starts a new index, so everything has to be looked up again, but assets that did not change
+    between versions are only stored once. Repeated runs against the same client version never
+    download an asset twice.
+
+    Layout of the cache directory:
+    - `blobs/<hash>.<extension>` the asset contents
+    - `versions/<client version>/<asset path>` the name of the blob holding the asset
+    - `staging/` downloads that have not been added yet
+*/
+#[derive(Debug, Clone)]
+pub struct AssetCache {
+    root: PathBuf,
+}
+
+impl AssetCache {
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    // The local path of an asset that has been cached for this client version.
+    pub async fn get(&self, client_version: &str, asset_path: &str) -> Result<Option<PathBuf>> {
+        let index_path = self.index_path(client_version, asset_path)?;
+
+        let blob_name = match tokio::fs::read_to_string(&index_path).await {
+            Ok(blob_name) => blob_name,
+            Err(_) => return Ok(None),
+        };
+
+        let blob_path = self.root.join(BLOBS_DIR).join(blob_name.trim());
+
+        // The blob may have been pruned by a process that was on another version
+        if tokio::fs::metadata(&blob_path).await.is_ok() {
+            Ok(Some(blob_path))
+        } else {
+            Ok(None)
+        }
+    }
+
+    // A new path to download an asset to before passing it to `insert`.
+    pub fn staging_path(&self, asset_path: &str) -> Result<PathBuf> {
+        let normalized = normalize_asset_path(asset_path)
+            .ok_or_else(|| LcuDriverError::InvalidAssetPath(asset_path.to_owned()))?;
+
+        let file_name = format!(
+            "{}-{}{}",
+            std::process::id(),
+            STAGING_COUNTER.fetch_add(1, Ordering::Relaxed),
+            extension(normalized)
+        );
+
+        Ok(self.root.join(STAGING_DIR).join(file_name))
+    }
+
+    /*
+        Move a downloaded asset into the cache and record it for the client version, returning the
+        path of its blob. The downloaded file is removed if an identical blob already exists.
+    */
+    pub async fn insert(
+        &self,
+        client_version: &str,
+        asset_path: &str,
+        downloaded: &Path,
+    ) -> Result<PathBuf> {
+        let index_path = self.index_path(client_version, asset_path)?;
+
+        let blob_name = format!("{}{}", hash_file(downloaded).await?, extension(asset_path));
+        let blob_path = self.root.join(BLOBS_DIR).join(&blob_name);
+
+        if tokio::fs::metadata(&blob_path).await.is_ok() {
+            tokio::fs::remove_file(downloaded).await?;
+        } else {
+            tokio::fs::create_dir_all(self.root.join(BLOBS_DIR)).await?;
+            tokio::fs::rename(downloaded, &blob_path).await?;
+        }
+
+        if let Some(parent) = index_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        // Written next to the index entry and renamed so that readers never see a partial name
+        let mut tmp_path = index_path.as_os_str().to_owned();
+        tmp_path.push(".part");
+
+        tokio::fs::write(&tmp_path, &blob_name).await?;
+        tokio::fs::rename(&tmp_path, &index_path).await?;
+
+        Ok(blob_path)
+    }
+
+    /*
+        Removes the indexes of every other client version, along with the blobs and staged downloads
+        that the given client version does not use.
+    */
+    pub async fn prune(&self, client_version: &str) -> Result<()> {
+        let current = version_dir(client_version)?;
+        let versions = self.root.join(VERSIONS_DIR);
+
+        if let Ok(mut entries) = tokio::fs::read_dir(&versions).await {
+            while let Some(entry) = entries.next_entry().await? {
+                if entry.file_name() != current.as_str() {
+                    tokio::fs::remove_dir_all(entry.path()).await?;
+                }
+            }
+        }
+
+        let mut used = Vec::new();
+
+        for index_path in files_under(&versions.join(&current)).await? {
+            used.push(
+                tokio::fs::read_to_string(index_path)
+                    .await?
+                    .trim()
+                    .to_owned(),
+            );
+        }
+
+        if let Ok(mut entries) = tokio::fs::read_dir(self.root.join(BLOBS_DIR)).await {
+            while let Some(entry) = entries.next_entry().await? {
+                if !used.iter().any(|name| entry.file_name() == name.as_str()) {
+                    tokio::fs::remove_file(entry.path()).await?;
+                }
+            }
+        }
+
+        match tokio::fs::remove_dir_all(self.root.join(STAGING_DIR)).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn index_path(&self, client_version: &str, asset_path: &str) -> Result<PathBuf> {
+        let normalized = normalize_asset_path(asset_path)
+            .ok_or_else(|| LcuDriverError::InvalidAssetPath(asset_path.to_owned()))?;
+
+        Ok(self
+            .root
+            .join(VERSIONS_DIR)
+            .join(version_dir(client_version)?)
+            .join(normalized))
+    }
+}
+
+// The client version as a single directory name that cannot refer to another directory.
+fn version_dir(client_version: &str) -> Result<String> {
+    match client_version.replace(['/', '\\'], "_") {
+        dir if dir.is_empty() || dir == "." || dir == ".." => Err(
+            LcuDriverError::InvalidClientVersion(client_version.to_owned()),
+        ),
+        dir => Ok(dir),
+    }
+}
+
+// The extension of the asset including the dot, kept so that cached files can be opened by type.
+fn extension(asset_path: &str) -> String {
+    Path::new(asset_path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| format!(".{}", extension))
+        .unwrap_or_default()
+}
+
+async fn hash_file(path: &Path) -> Result<String> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut context = Context::new(&SHA256);
+    let mut buffer = vec![0; 64 * 1024];
+
+    loop {
+        let read = file.read(&mut buffer).await?;
+
+        if read == 0 {
+            break;
+        }
+
+        context.update(&buffer[..read]);
+    }
+
+    Ok(context
+        .finish()
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+// Every file in a directory tree, which may not exist.
+async fn files_under(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+
+    while let Some(dir) = dirs.pop() {
+        let mut entries = match tokio::fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        while let Some(entry) = entries.next_entry().await? {
+            if entry.file_type().await?.is_dir() {
+                dirs.push(entry.path());
+            } else {
+                files.push(entry.path());
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::errors::LcuDriverError;
+    use crate::models::asset_cache::AssetCache;
+
+    const ICON: &str = "/lol-game-data/assets/v1/champion-icons/1.png";
+
+    fn temp_cache(name: &str) -> AssetCache {
+        let root = std::env::temp_dir().join(format!(
+            "lcu_driver_asset_cache_{}_{}",
+            name,
+            std::process::id()
+        ));
+
+        let _ = std::fs::remove_dir_all(&root);
+
+        AssetCache::new(root)
+    }
+
+    async fn download(cache: &AssetCache, contents: &str) -> PathBuf {
+        let path = cache.staging_path(ICON).unwrap();
+
+        tokio::fs::create_dir_all(path.parent().unwrap())
+            .await
+            .unwrap();
+        tokio::fs::write(&path, contents).await.unwrap();
+
+        path
+    }
+
+    #[test]
+    fn test_rejects_paths_outside_the_cache() {
+        let cache = AssetCache::new("./cache");
+
+        assert_eq!(
+            cache.staging_path("../lockfile"),
+            Err(LcuDriverError::InvalidAssetPath("../lockfile".to_owned()))
+        );
+
+        for version in ["", ".", ".."] {
+            assert_eq!(
+                cache.index_path(version, ICON),
+                Err(LcuDriverError::InvalidClientVersion(version.to_owned()))
+            );
+        }
+
+        assert!(cache.index_path("12.10.1/..", ICON).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_unchanged_assets_share_a_blob() {
+        let cache = temp_cache("share");
+
+        let downloaded = download(&cache, "icon").await;
+        let first = cache.insert("12.10.1", ICON, &downloaded).await.unwrap();
+
+        assert_eq!(
+            cache.get("12.10.1", ICON).await.unwrap(),
+            Some(first.clone())
+        );
+        assert_eq!(cache.get("12.11.1", ICON).await.unwrap(), None);
+        assert_eq!(first.extension().unwrap(), "png");
+
+        let downloaded = download(&cache, "icon").await;
+        let second = cache.insert("12.11.1", ICON, &downloaded).await.unwrap();
+
+        assert_eq!(first, second);
+        assert!(!downloaded.exists());
+
+        std::fs::remove_dir_all(cache.root()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_prune_keeps_blobs_of_current_version() {
+        let cache = temp_cache("prune");
+
+        let downloaded = download(&cache, "old icon").await;
+        let old = cache.insert("12.10.1", ICON, &downloaded).await.unwrap();
+
+        let downloaded = download(&cache, "new icon").await;
+        let new = cache.insert("12.11.1", ICON, &downloaded).await.unwrap();
+
+        cache.prune("12.11.1").await.unwrap();
+
+        assert!(!old.exists());
+        assert_eq!(cache.get("12.10.1", ICON).await.unwrap(), None);
+        assert_eq!(cache.get("12.11.1", ICON).await.unwrap(), Some(new));
+
+        std::fs::remove_dir_all(cache.root()).unwrap();
+    }
+}
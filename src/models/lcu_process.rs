@@ -24,6 +24,14 @@ impl LcuProcess {
         })
     }
 
+    // Used when the lockfile location is already known and the process does not need to be located
+    pub(crate) fn from_install_directory(install_directory: PathBuf) -> Self {
+        Self {
+            output: String::new(),
+            install_directory,
+        }
+    }
+
     // currently only detects league installed through lutris
     #[cfg(target_os = "linux")]
     async fn new_lutris(output: String) -> Result<Self> {
@@ -1,3 +1,6 @@
 pub mod api_error;
+pub mod asset_cache;
 pub mod lcu_process;
 pub mod lockfile;
+pub mod response_cache;
+pub(crate) mod single_flight;
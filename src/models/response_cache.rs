@@ -0,0 +1,374 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use reqwest::header::HeaderMap;
+use tokio::task::JoinHandle;
+
+use crate::logging::log_debug;
+use crate::transport::EventTransport;
+use crate::websocket::{LcuWebSocket, ALL_JSON_API_EVENTS};
+use crate::Result;
+
+// How long to wait before connecting the invalidation websocket again after it closed.
+const INVALIDATOR_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/*
+    Opt-in cache for GET responses of slow-changing endpoints. Only endpoints that have been given a
+    TTL are cached. Entries are invalidated when they expire, when the client publishes an event for
+    the same URI, when the driver modifies the resource, or when the driver reconnects to a new client.
+    The driver listens for events itself, so this does not depend on reading a websocket.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct ResponseCacheConfig {
+    ttls: HashMap<String, Duration>,
+    prefix_ttls: Vec<(String, Duration)>,
+}
+
+impl ResponseCacheConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Cache responses of the endpoint with exactly this URL, e.g. "/lol-perks/v1/inventory".
+    pub fn ttl<S: Into<String>>(mut self, url: S, ttl: Duration) -> Self {
+        self.ttls.insert(url.into(), ttl);
+        self
+    }
+
+    // Cache responses of every endpoint under this URL prefix, e.g. "/lol-game-data/assets/".
+    pub fn ttl_prefix<S: Into<String>>(mut self, prefix: S, ttl: Duration) -> Self {
+        self.prefix_ttls.push((prefix.into(), ttl));
+        self
+    }
+
+    fn ttl_for(&self, url: &str) -> Option<Duration> {
+        if let Some(ttl) = self.ttls.get(url) {
+            return Some(*ttl);
+        }
+
+        self.prefix_ttls
+            .iter()
+            .filter(|(prefix, _)| url.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, ttl)| *ttl)
+    }
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    body: String,
+    expires_at: Instant,
+}
+
+/*
+    The generation is bumped whenever the URL is invalidated, so that a response requested before the
+    invalidation is not cached once it arrives.
+*/
+#[derive(Debug, Default)]
+struct CacheSlot {
+    generation: u64,
+    entry: Option<CacheEntry>,
+}
+
+impl CacheSlot {
+    fn invalidate(&mut self) {
+        self.generation += 1;
+        self.entry = None;
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ResponseCache {
+    config: ResponseCacheConfig,
+    slots: Mutex<HashMap<String, CacheSlot>>,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(config: ResponseCacheConfig) -> Self {
+        Self {
+            config,
+            slots: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn is_cacheable(&self, url: &str) -> bool {
+        self.config.ttl_for(url).is_some()
+    }
+
+    pub(crate) fn get(&self, url: &str) -> Option<String> {
+        let mut slots = self.slots.lock().expect("Response cache lock poisoned");
+        let slot = slots.get_mut(url)?;
+
+        match &slot.entry {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.body.clone()),
+            Some(_) => {
+                slot.entry = None;
+                None
+            }
+            None => None,
+        }
+    }
+
+    // Read before requesting the URL and pass the result to `insert` along with the response.
+    pub(crate) fn generation(&self, url: &str) -> u64 {
+        self.slots
+            .lock()
+            .expect("Response cache lock poisoned")
+            .entry(url.to_owned())
+            .or_default()
+            .generation
+    }
+
+    // Cache the response unless the URL has been invalidated since `generation` was read.
+    pub(crate) fn insert(&self, url: &str, body: &str, generation: u64) {
+        if let Some(ttl) = self.config.ttl_for(url) {
+            let mut slots = self.slots.lock().expect("Response cache lock poisoned");
+            let slot = slots.entry(url.to_owned()).or_default();
+
+            if slot.generation != generation {
+                log_debug!(
+                    path = url,
+                    "Not caching response invalidated while in flight"
+                );
+                return;
+            }
+
+            slot.entry = Some(CacheEntry {
+                body: body.to_owned(),
+                expires_at: Instant::now() + ttl,
+            });
+        }
+    }
+
+    /*
+        Invalidate the given URI along with anything nested under it. A change to
+        "/lol-perks/v1/pages/5" also invalidates its collection "/lol-perks/v1/pages".
+    */
+    pub(crate) fn invalidate(&self, uri: &str) {
+        let parent = uri.rsplit_once('/').map(|(parent, _)| parent);
+
+        self.slots
+            .lock()
+            .expect("Response cache lock poisoned")
+            .iter_mut()
+            .filter(|(url, _)| {
+                let nested = url
+                    .strip_prefix(uri)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'));
+
+                nested || Some(url.as_str()) == parent
+            })
+            .for_each(|(_, slot)| slot.invalidate());
+    }
+
+    pub(crate) fn clear(&self) {
+        self.slots
+            .lock()
+            .expect("Response cache lock poisoned")
+            .values_mut()
+            .for_each(CacheSlot::invalidate);
+    }
+}
+
+/*
+    Task owned by the driver that invalidates the cache as the client publishes events. Anything
+    cached while it is not connected may have missed an event, so the cache is cleared whenever the
+    websocket closes. The task is stopped when this is dropped.
+*/
+#[derive(Debug)]
+pub(crate) struct CacheInvalidator {
+    task: JoinHandle<()>,
+}
+
+impl CacheInvalidator {
+    pub(crate) fn spawn(
+        cache: Arc<ResponseCache>,
+        event_transport: Arc<dyn EventTransport>,
+        url: url::Url,
+        headers: HeaderMap,
+    ) -> Self {
+        let task = tokio::spawn(async move {
+            loop {
+                #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+                if let Err(e) = Self::watch(
+                    &cache,
+                    event_transport.as_ref(),
+                    url.clone(),
+                    headers.clone(),
+                )
+                .await
+                {
+                    log_debug!(error = %e, "Response cache lost the client websocket");
+                }
+
+                cache.clear();
+
+                tokio::time::sleep(INVALIDATOR_RETRY_DELAY).await;
+            }
+        });
+
+        Self { task }
+    }
+
+    async fn watch(
+        cache: &ResponseCache,
+        event_transport: &dyn EventTransport,
+        url: url::Url,
+        headers: HeaderMap,
+    ) -> Result<()> {
+        // Interceptors are left out, this websocket is internal to the driver
+        let channel = event_transport.connect(url, headers).await?;
+        let mut websocket = LcuWebSocket::new(channel, Arc::new(Vec::new()));
+
+        websocket.subscribe(ALL_JSON_API_EVENTS).await?;
+
+        while let Some(event) = websocket.next_event().await {
+            if let Ok(event) = event {
+                cache.invalidate(&event.uri);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for CacheInvalidator {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::models::response_cache::{ResponseCache, ResponseCacheConfig};
+
+    fn test_cache() -> ResponseCache {
+        ResponseCache::new(
+            ResponseCacheConfig::new()
+                .ttl("/lol-perks/v1/inventory", Duration::from_secs(60))
+                .ttl("/lol-perks/v1/pages", Duration::from_secs(60))
+                .ttl("/lol-summoner/v1/current-summoner", Duration::ZERO)
+                .ttl_prefix("/lol-game-data/assets/", Duration::from_secs(60)),
+        )
+    }
+
+    #[test]
+    fn test_only_configured_urls_are_cached() {
+        let cache = test_cache();
+
+        cache.insert("/lol-perks/v1/inventory", "{}", 0);
+        cache.insert("/lol-gameflow/v1/session", "{}", 0);
+        cache.insert("/lol-game-data/assets/v1/perks.json", "[]", 0);
+
+        assert_eq!(cache.get("/lol-perks/v1/inventory"), Some("{}".to_owned()));
+        assert_eq!(cache.get("/lol-gameflow/v1/session"), None);
+        assert_eq!(
+            cache.get("/lol-game-data/assets/v1/perks.json"),
+            Some("[]".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_expired_entries_are_not_returned() {
+        let cache = test_cache();
+
+        cache.insert("/lol-summoner/v1/current-summoner", "{}", 0);
+
+        assert_eq!(cache.get("/lol-summoner/v1/current-summoner"), None);
+    }
+
+    #[test]
+    fn test_invalidate_uri_and_parent() {
+        let cache = test_cache();
+
+        cache.insert("/lol-perks/v1/inventory", "{}", 0);
+        cache.insert("/lol-perks/v1/pages", "[]", 0);
+
+        cache.invalidate("/lol-perks/v1/pages/5");
+
+        assert_eq!(cache.get("/lol-perks/v1/pages"), None);
+        assert_eq!(cache.get("/lol-perks/v1/inventory"), Some("{}".to_owned()));
+
+        cache.invalidate("/lol-perks/v1/inventory");
+
+        assert_eq!(cache.get("/lol-perks/v1/inventory"), None);
+    }
+
+    #[test]
+    fn test_response_invalidated_in_flight_is_not_cached() {
+        let cache = test_cache();
+        let generation = cache.generation("/lol-perks/v1/pages");
+
+        // An event arrives while the request is in flight
+        cache.invalidate("/lol-perks/v1/pages/5");
+        cache.insert("/lol-perks/v1/pages", "[]", generation);
+
+        assert_eq!(cache.get("/lol-perks/v1/pages"), None);
+
+        cache.insert(
+            "/lol-perks/v1/pages",
+            "[]",
+            cache.generation("/lol-perks/v1/pages"),
+        );
+
+        assert_eq!(cache.get("/lol-perks/v1/pages"), Some("[]".to_owned()));
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod driver_tests {
+    use std::time::Duration;
+
+    use reqwest::Method;
+
+    use crate::endpoints::fixtures::load_fixture;
+    use crate::models::response_cache::ResponseCacheConfig;
+    use crate::testing::{FakeTransport, MockResponse};
+    use crate::websocket::{LcuEventType, ALL_JSON_API_EVENTS};
+
+    const SUMMONER_PATH: &str = "/lol-summoner/v1/current-summoner";
+
+    #[tokio::test]
+    async fn test_events_invalidate_without_reading_a_websocket() {
+        let transport = FakeTransport::new();
+        let summoner = load_fixture("summoner/current_summoner.json");
+
+        transport.route(Method::GET, SUMMONER_PATH, MockResponse::json(&summoner));
+
+        let driver = transport
+            .driver_builder("./test_data/lockfile")
+            .response_cache(ResponseCacheConfig::new().ttl(SUMMONER_PATH, Duration::from_secs(60)))
+            .connect()
+            .await
+            .unwrap();
+
+        transport.wait_for_subscription(ALL_JSON_API_EVENTS).await;
+
+        let summoner_requests = || {
+            transport
+                .requests()
+                .iter()
+                .filter(|r| r.path == SUMMONER_PATH)
+                .count()
+        };
+
+        driver.get_current_summoner().await.unwrap();
+        driver.get_current_summoner().await.unwrap();
+
+        assert_eq!(summoner_requests(), 1);
+
+        transport.push_event(SUMMONER_PATH, LcuEventType::Update, &summoner);
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while summoner_requests() < 2 {
+                driver.get_current_summoner().await.unwrap();
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("Cached response was not invalidated");
+    }
+}
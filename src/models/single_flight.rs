@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+
+use tokio::sync::broadcast;
+
+use crate::Result;
+
+/*
+    Coalesces identical requests that are in flight at the same time. The first caller for a key
+    performs the request and every caller that arrives before it finishes receives a copy of the
+    same result instead of sending its own request.
+*/
+#[derive(Debug, Default)]
+pub(crate) struct SingleFlight {
+    in_flight: Mutex<HashMap<String, broadcast::Sender<Result<String>>>>,
+}
+
+enum Flight<'a> {
+    Leader(InFlightGuard<'a>),
+    Follower(broadcast::Receiver<Result<String>>),
+}
+
+// Removes the in-flight entry even if the leading request is cancelled, waking up any followers.
+struct InFlightGuard<'a> {
+    single_flight: &'a SingleFlight,
+    key: String,
+    sender: broadcast::Sender<Result<String>>,
+}
+
+impl SingleFlight {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) async fn run<F, Fut>(&self, key: &str, request: F) -> Result<String>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<String>>,
+    {
+        let mut request = Some(request);
+
+        loop {
+            match self.join(key) {
+                Flight::Leader(guard) => {
+                    let request = request.take().expect("Request is only sent once");
+                    let result = request().await;
+
+                    self.finish(guard, result.clone());
+
+                    return result;
+                }
+                Flight::Follower(mut receiver) => {
+                    if let Ok(result) = receiver.recv().await {
+                        return result;
+                    }
+
+                    // The leading request was cancelled, try again
+                }
+            }
+        }
+    }
+
+    fn join(&self, key: &str) -> Flight<'_> {
+        let mut in_flight = self.in_flight.lock().expect("Single flight lock poisoned");
+
+        match in_flight.get(key) {
+            Some(sender) => Flight::Follower(sender.subscribe()),
+            None => {
+                let (sender, _) = broadcast::channel(1);
+
+                in_flight.insert(key.to_owned(), sender.clone());
+
+                Flight::Leader(InFlightGuard {
+                    single_flight: self,
+                    key: key.to_owned(),
+                    sender,
+                })
+            }
+        }
+    }
+
+    fn finish(&self, guard: InFlightGuard<'_>, result: Result<String>) {
+        guard.remove();
+
+        let _ = guard.sender.send(result);
+    }
+}
+
+impl InFlightGuard<'_> {
+    fn remove(&self) {
+        let mut in_flight = self
+            .single_flight
+            .in_flight
+            .lock()
+            .expect("Single flight lock poisoned");
+
+        if in_flight
+            .get(&self.key)
+            .is_some_and(|sender| sender.same_channel(&self.sender))
+        {
+            in_flight.remove(&self.key);
+        }
+    }
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.remove();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use tokio::sync::Notify;
+
+    use crate::errors::LcuDriverError;
+    use crate::models::single_flight::SingleFlight;
+
+    #[tokio::test]
+    async fn test_concurrent_requests_are_coalesced() {
+        let single_flight = SingleFlight::new();
+        let sent = AtomicUsize::new(0);
+        let release = Notify::new();
+
+        let request = || async {
+            sent.fetch_add(1, Ordering::SeqCst);
+            release.notified().await;
+
+            Ok("{}".to_owned())
+        };
+
+        let (first, second, _) = tokio::join!(
+            single_flight.run("/lol-perks/v1/inventory", request),
+            single_flight.run("/lol-perks/v1/inventory", request),
+            async { release.notify_one() }
+        );
+
+        assert_eq!(sent.load(Ordering::SeqCst), 1);
+        assert_eq!(first, Ok("{}".to_owned()));
+        assert_eq!(second, Ok("{}".to_owned()));
+    }
+
+    #[tokio::test]
+    async fn test_sequential_requests_are_not_coalesced() {
+        let single_flight = SingleFlight::new();
+        let sent = AtomicUsize::new(0);
+
+        let request = || async {
+            sent.fetch_add(1, Ordering::SeqCst);
+
+            Err(LcuDriverError::FailedToSendRequest("refused".to_owned()))
+        };
+
+        for _ in 0..2 {
+            assert!(single_flight
+                .run("/lol-perks/v1/inventory", request)
+                .await
+                .is_err());
+        }
+
+        assert_eq!(sent.load(Ordering::SeqCst), 2);
+    }
+}
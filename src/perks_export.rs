@@ -0,0 +1,445 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::catalog::GameDataCatalogs;
+use crate::endpoints::ids::{PerkId, PerkStyleId};
+use crate::endpoints::perks::{invalid_page, PerksPage, PerksPageBuilder};
+use crate::errors::LcuDriverError;
+use crate::logging::log_warn;
+use crate::Result;
+
+// Bumped whenever a change to `ExportedPerksPage` or the share code would break older files.
+pub const EXPORT_FORMAT_VERSION: u32 = 1;
+
+// Keystone, three primary perks, two secondary perks and three stat shards.
+const PAGE_PERK_COUNT: usize = 9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Toml,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Toml => "toml",
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "json" => Some(ExportFormat::Json),
+            "toml" => Some(ExportFormat::Toml),
+            _ => None,
+        }
+    }
+}
+
+/*
+    A rune page as it is written to disk. Styles and perks are stored by name rather than by ID so the
+    files can be read and edited by hand, and names are matched case-insensitively when importing.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedPerksPage {
+    pub version: u32,
+    pub name: String,
+    pub primary_style: String,
+    pub keystone: String,
+    pub primary_perks: Vec<String>,
+    pub secondary_style: String,
+    pub secondary_perks: Vec<String>,
+    pub stat_shards: Vec<String>,
+}
+
+impl ExportedPerksPage {
+    pub fn from_page(page: &PerksPage, catalogs: &GameDataCatalogs) -> Result<Self> {
+        let perks = page_perks(page)?;
+        let style_name = |id| {
+            catalogs
+                .perk_styles
+                .name_of(id)
+                .map(str::to_owned)
+                .ok_or_else(|| invalid_page(format!("style {} does not exist", id)))
+        };
+        let perk_names = |ids: &[PerkId]| {
+            ids.iter()
+                .map(|&id| {
+                    catalogs
+                        .perks
+                        .name_of(id)
+                        .map(str::to_owned)
+                        .ok_or_else(|| invalid_page(format!("perk {} does not exist", id)))
+                })
+                .collect::<Result<Vec<String>>>()
+        };
+
+        Ok(Self {
+            version: EXPORT_FORMAT_VERSION,
+            name: page.name.clone(),
+            primary_style: style_name(page.primary_style_id)?,
+            keystone: perk_names(&perks[..1])?.remove(0),
+            primary_perks: perk_names(&perks[1..4])?,
+            secondary_style: style_name(page.sub_style_id)?,
+            secondary_perks: perk_names(&perks[4..6])?,
+            stat_shards: perk_names(&perks[6..])?,
+        })
+    }
+
+    // The builder still has to be validated against the client's perks with `build`.
+    pub fn to_builder(&self, catalogs: &GameDataCatalogs) -> Result<PerksPageBuilder> {
+        if self.version != EXPORT_FORMAT_VERSION {
+            return Err(invalid_page(format!(
+                "unsupported export format version {}",
+                self.version
+            )));
+        }
+
+        let style_id = |name: &str| {
+            catalogs
+                .perk_styles
+                .id_of(name)
+                .ok_or_else(|| invalid_page(format!("unknown style {}", name)))
+        };
+        let perk_ids = |names: &[String]| {
+            names
+                .iter()
+                .map(|name| {
+                    catalogs
+                        .perks
+                        .id_of(name)
+                        .ok_or_else(|| invalid_page(format!("unknown perk {}", name)))
+                })
+                .collect::<Result<Vec<PerkId>>>()
+        };
+
+        let mut perks = perk_ids(std::slice::from_ref(&self.keystone))?;
+        perks.extend(perk_ids(&self.primary_perks)?);
+        perks.extend(perk_ids(&self.secondary_perks)?);
+        perks.extend(perk_ids(&self.stat_shards)?);
+
+        builder_from_ids(
+            &self.name,
+            style_id(&self.primary_style)?,
+            style_id(&self.secondary_style)?,
+            &perks,
+        )
+    }
+
+    pub fn to_file_contents(&self, format: ExportFormat) -> Result<String> {
+        match format {
+            ExportFormat::Json => Ok(serde_json::to_string_pretty(self)?),
+            ExportFormat::Toml => Ok(toml::to_string(self)?),
+        }
+    }
+
+    pub fn from_file_contents(contents: &str, format: ExportFormat) -> Result<Self> {
+        match format {
+            ExportFormat::Json => Ok(serde_json::from_str(contents)?),
+            ExportFormat::Toml => Ok(toml::from_str(contents)?),
+        }
+    }
+}
+
+/*
+    A short string to paste into chat. It holds the format version, both styles and the nine perks as
+    big endian 16 bit IDs followed by the page name, encoded as URL safe base64.
+*/
+pub fn encode_share_code(page: &PerksPage) -> Result<String> {
+    let perks = page_perks(page)?;
+
+    let mut bytes = vec![EXPORT_FORMAT_VERSION as u8];
+
+    for id in [page.primary_style_id.get(), page.sub_style_id.get()]
+        .into_iter()
+        .chain(perks.iter().map(|id| id.get()))
+    {
+        let id = u16::try_from(id)
+            .map_err(|_| invalid_page(format!("{} is too large for a share code", id)))?;
+
+        bytes.extend(id.to_be_bytes());
+    }
+
+    bytes.extend(page.name.as_bytes());
+
+    Ok(base64::encode_config(bytes, base64::URL_SAFE_NO_PAD))
+}
+
+pub fn decode_share_code(code: &str) -> Result<PerksPageBuilder> {
+    let bytes = base64::decode_config(code.trim(), base64::URL_SAFE_NO_PAD)
+        .map_err(|e| invalid_page(format!("malformed share code - {}", e)))?;
+
+    let ids_end = 1 + 2 * (2 + PAGE_PERK_COUNT);
+
+    if bytes.len() < ids_end {
+        return Err(invalid_page("share code is too short"));
+    }
+
+    if bytes[0] as u32 != EXPORT_FORMAT_VERSION {
+        return Err(invalid_page(format!(
+            "unsupported share code version {}",
+            bytes[0]
+        )));
+    }
+
+    let ids: Vec<u32> = bytes[1..ids_end]
+        .chunks_exact(2)
+        .map(|id| u16::from_be_bytes([id[0], id[1]]) as u32)
+        .collect();
+    let name = String::from_utf8(bytes[ids_end..].to_vec())?;
+    let perks: Vec<PerkId> = ids[2..].iter().map(|&id| PerkId(id)).collect();
+
+    builder_from_ids(&name, PerkStyleId(ids[0]), PerkStyleId(ids[1]), &perks)
+}
+
+// Page names can contain anything, so only a safe subset of each name is used for its file.
+pub fn export_file_name(name: &str, format: ExportFormat, taken: &mut HashSet<String>) -> String {
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let stem = if stem.is_empty() {
+        "page".to_owned()
+    } else {
+        stem
+    };
+
+    let mut file_name = format!("{}.{}", stem, format.extension());
+    let mut suffix = 2;
+
+    while !taken.insert(file_name.to_lowercase()) {
+        file_name = format!("{}-{}.{}", stem, suffix, format.extension());
+        suffix += 1;
+    }
+
+    file_name
+}
+
+/*
+    The editable pages, ready to be written. The client's own pages cannot be imported again, so they
+    are left out, and pages that cannot be exported are skipped rather than failing the whole export.
+*/
+pub fn export_pages(pages: &[PerksPage], catalogs: &GameDataCatalogs) -> Vec<ExportedPerksPage> {
+    let mut exported = Vec::with_capacity(pages.len());
+
+    for page in pages.iter().filter(|page| page.is_editable) {
+        match ExportedPerksPage::from_page(page, catalogs) {
+            Ok(page) => exported.push(page),
+            #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+            Err(e) => {
+                log_warn!(error = %e, name = %page.name, "Skipping rune page that cannot be exported");
+            }
+        }
+    }
+
+    exported
+}
+
+pub async fn write_exported_pages(
+    dir: &Path,
+    pages: &[ExportedPerksPage],
+    format: ExportFormat,
+) -> Result<Vec<PathBuf>> {
+    tokio::fs::create_dir_all(dir).await?;
+
+    let mut taken = HashSet::new();
+    let mut paths = Vec::with_capacity(pages.len());
+
+    for page in pages {
+        let path = dir.join(export_file_name(&page.name, format, &mut taken));
+
+        tokio::fs::write(&path, page.to_file_contents(format)?).await?;
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
+
+// Every JSON and TOML file in the directory, in file name order. Other files are ignored.
+pub async fn read_exported_pages(dir: &Path) -> Result<Vec<ExportedPerksPage>> {
+    let mut entries = tokio::fs::read_dir(dir).await?;
+    let mut paths = Vec::new();
+
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+
+        if let Some(format) = ExportFormat::from_path(&path) {
+            paths.push((path, format));
+        }
+    }
+
+    paths.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut pages = Vec::with_capacity(paths.len());
+
+    for (path, format) in paths {
+        let contents = tokio::fs::read_to_string(&path).await?;
+
+        pages.push(
+            ExportedPerksPage::from_file_contents(&contents, format).map_err(|e| {
+                LcuDriverError::new(format!("Failed to read {} - {}", path.display(), e))
+            })?,
+        );
+    }
+
+    Ok(pages)
+}
+
+fn page_perks(page: &PerksPage) -> Result<&[PerkId]> {
+    if page.selected_perk_ids.len() != PAGE_PERK_COUNT {
+        return Err(invalid_page(format!(
+            "{} has {} perks instead of {}",
+            page.name,
+            page.selected_perk_ids.len(),
+            PAGE_PERK_COUNT
+        )));
+    }
+
+    Ok(&page.selected_perk_ids)
+}
+
+fn builder_from_ids(
+    name: &str,
+    primary_style_id: PerkStyleId,
+    sub_style_id: PerkStyleId,
+    perks: &[PerkId],
+) -> Result<PerksPageBuilder> {
+    if perks.len() != PAGE_PERK_COUNT {
+        return Err(invalid_page(format!(
+            "{} perks were given instead of {}",
+            perks.len(),
+            PAGE_PERK_COUNT
+        )));
+    }
+
+    Ok(PerksPageBuilder::new(name)
+        .primary_style(primary_style_id)
+        .keystone(perks[0])
+        .primary_perks([perks[1], perks[2], perks[3]])
+        .secondary_style(sub_style_id)
+        .secondary_perks([perks[4], perks[5]])
+        .stat_shards([perks[6], perks[7], perks[8]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::catalog::{Catalog, GameDataCatalogs};
+    use crate::endpoints::fixtures::{assert_round_trip, build_perks_page, perk_fixtures};
+    use crate::endpoints::perks::PerksPage;
+    use crate::perks_export::{
+        decode_share_code, encode_share_code, export_file_name, export_pages, ExportFormat,
+        ExportedPerksPage,
+    };
+
+    fn catalogs() -> GameDataCatalogs {
+        let (styles, perks) = perk_fixtures();
+
+        GameDataCatalogs {
+            client_version: "12.11.1".to_owned(),
+            champions: Catalog::new(Vec::new()),
+            summoner_spells: Catalog::new(Vec::new()),
+            perks: Catalog::new(perks),
+            perk_styles: Catalog::new(styles),
+        }
+    }
+
+    #[test]
+    fn test_pages_round_trip_through_files() {
+        let catalogs = catalogs();
+        let pages: Vec<PerksPage> = assert_round_trip("perks/pages.json");
+        let exported = ExportedPerksPage::from_page(&pages[0], &catalogs).unwrap();
+
+        assert_eq!(exported.keystone, "Conqueror");
+        assert_eq!(
+            exported.secondary_perks,
+            ["Sudden Impact", "Ravenous Hunter"]
+        );
+
+        for format in [ExportFormat::Json, ExportFormat::Toml] {
+            let contents = exported.to_file_contents(format).unwrap();
+            let imported = ExportedPerksPage::from_file_contents(&contents, format).unwrap();
+
+            assert_eq!(imported, exported);
+            assert_eq!(
+                build_perks_page(imported.to_builder(&catalogs).unwrap()).unwrap(),
+                pages[0]
+            );
+        }
+    }
+
+    #[test]
+    fn test_only_editable_pages_that_convert_are_exported() {
+        let mut pages: Vec<PerksPage> = assert_round_trip("perks/pages.json");
+
+        pages[1].selected_perk_ids.pop();
+
+        let exported = export_pages(&pages, &catalogs());
+
+        assert_eq!(exported.len(), 1);
+        assert_eq!(exported[0].name, "Jungle Conqueror");
+    }
+
+    #[test]
+    fn test_names_are_matched_case_insensitively() {
+        let toml = r#"
+            version = 1
+            name = "Jungle Conqueror"
+            primary_style = "precision"
+            keystone = "conqueror"
+            primary_perks = ["triumph", "legend: alacrity", "last stand"]
+            secondary_style = "domination"
+            secondary_perks = ["sudden impact", "ravenous hunter"]
+            stat_shards = ["attack speed", "adaptive force", "armor"]
+        "#;
+        let pages: Vec<PerksPage> = assert_round_trip("perks/pages.json");
+        let imported = ExportedPerksPage::from_file_contents(toml, ExportFormat::Toml).unwrap();
+
+        assert_eq!(
+            build_perks_page(imported.to_builder(&catalogs()).unwrap()).unwrap(),
+            pages[0]
+        );
+    }
+
+    #[test]
+    fn test_share_code_round_trip() {
+        let pages: Vec<PerksPage> = assert_round_trip("perks/pages.json");
+        let code = encode_share_code(&pages[0]).unwrap();
+
+        assert!(!code.contains(['+', '/', '=']));
+        assert_eq!(
+            build_perks_page(decode_share_code(&code).unwrap()).unwrap(),
+            pages[0]
+        );
+        assert!(decode_share_code(&code[..20]).is_err());
+    }
+
+    #[test]
+    fn test_export_file_names_are_unique() {
+        let mut taken = HashSet::new();
+
+        assert_eq!(
+            export_file_name("Jungle: Conqueror", ExportFormat::Toml, &mut taken),
+            "Jungle__Conqueror.toml"
+        );
+        assert_eq!(
+            export_file_name("Jungle/ Conqueror", ExportFormat::Toml, &mut taken),
+            "Jungle__Conqueror-2.toml"
+        );
+        assert_eq!(
+            export_file_name("", ExportFormat::Json, &mut taken),
+            "page.json"
+        );
+    }
+}
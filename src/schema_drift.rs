@@ -0,0 +1,410 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::endpoints::champ_select::{ChampSelectEndpoint, ChampSelectSession, SkinCarouselSkin};
+use crate::endpoints::game_data::{
+    ChampionSummary, GameDataEndpoint, PerkData, PerkStyle, PerkStyles, SummonerSpellData,
+};
+use crate::endpoints::gameflow::{GameFlowEndpoint, GameFlowPhase, GameFlowSession};
+use crate::endpoints::ids::ChampionId;
+use crate::endpoints::lobby::{Lobby, LobbyEndpoint, LobbyInvitation};
+use crate::endpoints::matchmaking::{MatchmakingEndpoint, MatchmakingSearch, ReadyCheck};
+use crate::endpoints::patch::PatchEndpoint;
+use crate::endpoints::perks::{PerksEndpoint, PerksInventory, PerksPage};
+use crate::endpoints::summoner::{Summoner, SummonerEndpoint};
+use crate::endpoints::EndpointInfo;
+use crate::{Initialized, LcuDriver, Result};
+
+/*
+    A comparison between what the client returned for every modeled endpoint and what our models
+    expect. Reports are saved as JSON with stable field paths, so reports from consecutive patches
+    can be diffed to see exactly what changed.
+*/
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaDriftReport {
+    pub game_version: Option<String>,
+    pub endpoints: Vec<EndpointDrift>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointDrift {
+    pub endpoint: String,
+    // Set when the endpoint could not be fetched, e.g. the champ select session in the lobby
+    pub error: Option<String>,
+    pub issues: Vec<DriftIssue>,
+}
+
+/*
+    Paths are dot separated field names as they appear in the JSON, with `[]` standing in for any
+    array element. The root of the payload is `$`.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum DriftIssue {
+    // The client sent a field the model does not have
+    UnknownField {
+        path: String,
+    },
+    // The model has a field the client did not send
+    MissingField {
+        path: String,
+    },
+    // The client sent a field with a different type than the model expects
+    TypeMismatch {
+        path: String,
+        expected: String,
+        found: String,
+    },
+    // The client sent a value the model cannot represent, such as an unknown enum variant
+    InvalidValue {
+        path: String,
+        message: String,
+    },
+}
+
+impl SchemaDriftReport {
+    pub fn has_drift(&self) -> bool {
+        self.endpoints.iter().any(|e| !e.issues.is_empty())
+    }
+
+    pub async fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let contents = tokio::fs::read_to_string(path).await?;
+
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    pub async fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        tokio::fs::write(path, serde_json::to_string_pretty(self)?).await?;
+
+        Ok(())
+    }
+}
+
+// Fetch every modeled endpoint from the client and compare the responses against the models.
+pub async fn detect_schema_drift(driver: &LcuDriver<Initialized>) -> SchemaDriftReport {
+    let endpoints = vec![
+        check_endpoint::<Summoner>(driver, SummonerEndpoint::Current.info()).await,
+        check_endpoint::<GameFlowSession>(driver, GameFlowEndpoint::Session.info()).await,
+        check_endpoint::<GameFlowPhase>(driver, GameFlowEndpoint::Phase.info()).await,
+        check_endpoint::<Lobby>(driver, LobbyEndpoint::Lobby.info()).await,
+        check_endpoint::<Vec<LobbyInvitation>>(driver, LobbyEndpoint::Invitations.info()).await,
+        check_endpoint::<MatchmakingSearch>(driver, MatchmakingEndpoint::Search.info()).await,
+        check_endpoint::<ReadyCheck>(driver, MatchmakingEndpoint::ReadyCheck.info()).await,
+        check_endpoint::<ChampSelectSession>(driver, ChampSelectEndpoint::Session.info()).await,
+        check_endpoint::<Vec<SkinCarouselSkin>>(
+            driver,
+            ChampSelectEndpoint::SkinCarouselSkins.info(),
+        )
+        .await,
+        check_endpoint::<Vec<ChampionId>>(driver, ChampSelectEndpoint::PickableChampionIds.info())
+            .await,
+        check_endpoint::<Vec<ChampionId>>(driver, ChampSelectEndpoint::BannableChampionIds.info())
+            .await,
+        check_endpoint::<Vec<ChampionId>>(driver, ChampSelectEndpoint::DisabledChampionIds.info())
+            .await,
+        check_endpoint::<PerksInventory>(driver, PerksEndpoint::Inventory.info()).await,
+        check_endpoint::<Vec<PerksPage>>(
+            driver,
+            PerksEndpoint::Pages(reqwest::Method::GET, None).info(),
+        )
+        .await,
+        check_endpoint::<Vec<PerkStyle>>(driver, PerksEndpoint::Styles.info()).await,
+        check_endpoint::<Vec<PerkData>>(driver, PerksEndpoint::Perks.info()).await,
+        check_endpoint::<Vec<ChampionSummary>>(driver, GameDataEndpoint::ChampionSummary.info())
+            .await,
+        check_endpoint::<Vec<SummonerSpellData>>(driver, GameDataEndpoint::SummonerSpells.info())
+            .await,
+        check_endpoint::<Vec<PerkData>>(driver, GameDataEndpoint::Perks.info()).await,
+        check_endpoint::<PerkStyles>(driver, GameDataEndpoint::PerkStyles.info()).await,
+        check_endpoint::<String>(driver, PatchEndpoint::GameVersion.info()).await,
+    ];
+
+    SchemaDriftReport {
+        game_version: driver.get_game_version().await.ok(),
+        endpoints,
+    }
+}
+
+async fn check_endpoint<T: Serialize + DeserializeOwned>(
+    driver: &LcuDriver<Initialized>,
+    endpoint_info: EndpointInfo,
+) -> EndpointDrift {
+    let endpoint = endpoint_info.url.clone();
+
+    let payload = driver
+        .get_endpoint(endpoint_info)
+        .await
+        .and_then(|body| Ok(serde_json::from_str::<Value>(&body)?));
+
+    match payload {
+        Ok(payload) => EndpointDrift {
+            endpoint,
+            error: None,
+            issues: compare_with_model::<T>(&payload),
+        },
+        Err(e) => EndpointDrift {
+            endpoint,
+            error: Some(e.to_string()),
+            issues: Vec::new(),
+        },
+    }
+}
+
+/*
+    Compare a payload against the model `T`. When the payload deserializes, it is serialized again
+    and the two are diffed for unknown and missing fields. When it does not, serde stops at the
+    first problem, so at most one issue is reported for it.
+*/
+pub fn compare_with_model<T: Serialize + DeserializeOwned>(payload: &Value) -> Vec<DriftIssue> {
+    // Deserialize from pretty printed text so the error position maps back to a single field
+    let pretty = serde_json::to_string_pretty(payload).unwrap_or_default();
+
+    let model = match serde_json::from_str::<T>(&pretty) {
+        Ok(model) => model,
+        Err(e) => return vec![issue_from_error(&pretty, &e)],
+    };
+
+    let serialized = match serde_json::to_value(&model) {
+        Ok(serialized) => serialized,
+        Err(e) => {
+            return vec![DriftIssue::InvalidValue {
+                path: "$".to_owned(),
+                message: e.to_string(),
+            }]
+        }
+    };
+
+    let mut issues = BTreeSet::new();
+    diff(payload, &serialized, "$", &mut issues);
+
+    issues.into_iter().collect()
+}
+
+fn diff(payload: &Value, model: &Value, path: &str, issues: &mut BTreeSet<DriftIssue>) {
+    match (payload, model) {
+        (Value::Object(payload), Value::Object(model)) => {
+            for (key, value) in payload {
+                let field = format!("{}.{}", path, key);
+
+                match model.get(key) {
+                    Some(expected) => diff(value, expected, &field, issues),
+                    None => {
+                        issues.insert(DriftIssue::UnknownField { path: field });
+                    }
+                }
+            }
+
+            for key in model.keys().filter(|key| !payload.contains_key(*key)) {
+                issues.insert(DriftIssue::MissingField {
+                    path: format!("{}.{}", path, key),
+                });
+            }
+        }
+        (Value::Array(payload), Value::Array(model)) => {
+            let element = format!("{}[]", path);
+
+            for (value, expected) in payload.iter().zip(model) {
+                diff(value, expected, &element, issues);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn issue_from_error(pretty: &str, error: &serde_json::Error) -> DriftIssue {
+    let message = error.to_string();
+    let message = match message.rsplit_once(" at line ") {
+        Some((message, _)) => message,
+        None => &message,
+    };
+
+    let path = path_at_line(pretty, error.line());
+
+    if let Some(field) = message
+        .strip_prefix("missing field `")
+        .and_then(|m| m.strip_suffix('`'))
+    {
+        // Missing fields are reported at the end of the object that should contain them
+        return DriftIssue::MissingField {
+            path: format!("{}.{}", path, field),
+        };
+    }
+
+    if let Some((found, expected)) = message
+        .strip_prefix("invalid type: ")
+        .and_then(|m| m.split_once(", expected "))
+    {
+        return DriftIssue::TypeMismatch {
+            path,
+            expected: expected.to_owned(),
+            found: found.to_owned(),
+        };
+    }
+
+    DriftIssue::InvalidValue {
+        path,
+        message: message.to_owned(),
+    }
+}
+
+/*
+    Find the path of the field on the given line of pretty printed JSON. Pretty printing puts every
+    field and every array element on its own line, so the path can be recovered by tracking which
+    objects and arrays are open. A closing bracket belongs to the container it closes.
+*/
+fn path_at_line(pretty: &str, line: usize) -> String {
+    // The path of every open container and whether it is an array
+    let mut open: Vec<(String, bool)> = Vec::new();
+    let mut path = "$".to_owned();
+
+    for text in pretty.lines().take(line) {
+        let text = text.trim().trim_end_matches(',');
+
+        if text.starts_with('}') || text.starts_with(']') {
+            path = open.pop().map(|(p, _)| p).unwrap_or_else(|| "$".to_owned());
+            continue;
+        }
+
+        let (key, value) = split_key(text);
+
+        path = match (open.last(), key) {
+            (Some((parent, true)), _) => format!("{}[]", parent),
+            (Some((parent, false)), Some(key)) => format!("{}.{}", parent, key),
+            _ => "$".to_owned(),
+        };
+
+        if value == "{" || value == "[" {
+            open.push((path.clone(), value == "["));
+        }
+    }
+
+    path
+}
+
+// Split a line of pretty printed JSON into its key, if it has one, and its value.
+fn split_key(text: &str) -> (Option<String>, &str) {
+    if !text.starts_with('"') {
+        return (None, text);
+    }
+
+    let mut escaped = false;
+
+    for (i, c) in text.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => {
+                return match text[i + 1..].strip_prefix(": ") {
+                    Some(value) => (serde_json::from_str(&text[..=i]).ok(), value),
+                    None => (None, text),
+                };
+            }
+            _ => {}
+        }
+    }
+
+    (None, text)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    use crate::schema_drift::{compare_with_model, DriftIssue};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Session {
+        game_id: isize,
+        timer: Timer,
+        my_team: Vec<Player>,
+        chat_room_name: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Timer {
+        phase: String,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Player {
+        cell_id: isize,
+        champion_id: isize,
+    }
+
+    #[test]
+    fn test_unknown_and_missing_fields() {
+        let payload = json!({
+            "gameId": 1,
+            "timer": { "phase": "BAN_PICK", "isInfinite": false },
+            "myTeam": [
+                { "cellId": 0, "championId": 1, "puuid": "a" },
+                { "cellId": 1, "championId": 2, "puuid": "b" }
+            ],
+            "recoveryCounter": 0
+        });
+
+        assert_eq!(
+            compare_with_model::<Session>(&payload),
+            vec![
+                DriftIssue::UnknownField {
+                    path: "$.myTeam[].puuid".to_owned()
+                },
+                DriftIssue::UnknownField {
+                    path: "$.recoveryCounter".to_owned()
+                },
+                DriftIssue::UnknownField {
+                    path: "$.timer.isInfinite".to_owned()
+                },
+                DriftIssue::MissingField {
+                    path: "$.chatRoomName".to_owned()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_required_field() {
+        let payload = json!({
+            "gameId": 1,
+            "timer": { "phase": "BAN_PICK" },
+            "myTeam": [{ "cellId": 0, "championId": 1 }, { "cellId": 1 }],
+            "chatRoomName": null
+        });
+
+        assert_eq!(
+            compare_with_model::<Session>(&payload),
+            vec![DriftIssue::MissingField {
+                path: "$.myTeam[].championId".to_owned()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_type_mismatch() {
+        let payload = json!({
+            "gameId": 1,
+            "timer": { "phase": 3 },
+            "myTeam": [],
+            "chatRoomName": "c1"
+        });
+
+        assert_eq!(
+            compare_with_model::<Session>(&payload),
+            vec![DriftIssue::TypeMismatch {
+                path: "$.timer.phase".to_owned(),
+                expected: "a string".to_owned(),
+                found: "integer `3`".to_owned(),
+            }]
+        );
+    }
+}
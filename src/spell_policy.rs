@@ -0,0 +1,446 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use crate::champ_select_tracker::CHAMP_SELECT_SESSION_URI;
+use crate::endpoints::champ_select::{ChampSelectSession, MySelection, PlayerSelection};
+use crate::endpoints::gameflow::GameMode;
+use crate::endpoints::ids::{ChampionId, SkinId, SpellId};
+use crate::endpoints::lobby::LobbyPosition;
+use crate::event_loop::{watch_uri, UriHandler};
+use crate::logging::{log_debug, log_info, log_warn};
+use crate::{Initialized, LcuDriver, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpellKey {
+    D,
+    F,
+}
+
+/*
+    Chooses summoner spells from per-champion, per-position and per-mode preferences, in that order of
+    precedence, then the defaults. Each preference lists one or two spells, and the two slots are filled
+    from the first preferences that apply, skipping spells the game mode does not allow. A slot that no
+    preference fills keeps the spell that is already selected. Key preferences, such as Flash on D, are
+    applied last.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct SpellPolicy {
+    champions: HashMap<ChampionId, Vec<SpellId>>,
+    positions: HashMap<LobbyPosition, Vec<SpellId>>,
+    modes: HashMap<GameMode, Vec<SpellId>>,
+    defaults: Vec<SpellId>,
+    keys: Vec<(SpellId, SpellKey)>,
+}
+
+impl SpellPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn champion(mut self, champion_id: ChampionId, spell_ids: Vec<SpellId>) -> Self {
+        self.champions.insert(champion_id, spell_ids);
+        self
+    }
+
+    pub fn position(mut self, position: LobbyPosition, spell_ids: Vec<SpellId>) -> Self {
+        self.positions.insert(position, spell_ids);
+        self
+    }
+
+    pub fn mode(mut self, game_mode: GameMode, spell_ids: Vec<SpellId>) -> Self {
+        self.modes.insert(game_mode, spell_ids);
+        self
+    }
+
+    pub fn defaults(mut self, spell_ids: Vec<SpellId>) -> Self {
+        self.defaults = spell_ids;
+        self
+    }
+
+    // Keep a spell on this key whenever it is selected, later calls win.
+    pub fn key(mut self, spell_id: SpellId, key: SpellKey) -> Self {
+        self.keys.push((spell_id, key));
+        self
+    }
+
+    // The spells to use on D and F.
+    pub fn spells_for(
+        &self,
+        game_mode: GameMode,
+        position: LobbyPosition,
+        champion_id: ChampionId,
+        current: (SpellId, SpellId),
+    ) -> (SpellId, SpellId) {
+        let disallowed = game_mode.disallowed_summoner_spells().unwrap_or_default();
+        let preferences = [
+            self.champions.get(&champion_id),
+            self.positions.get(&position),
+            self.modes.get(&game_mode),
+            Some(&self.defaults),
+        ];
+
+        let mut chosen: Vec<SpellId> = Vec::with_capacity(2);
+
+        for spell_id in preferences
+            .into_iter()
+            .flatten()
+            .flatten()
+            .chain([current.0, current.1].iter())
+        {
+            if chosen.len() == 2 {
+                break;
+            }
+
+            if disallowed.contains(spell_id) {
+                log_debug!(
+                    %spell_id,
+                    ?game_mode,
+                    "Skipping spell the game mode does not allow"
+                );
+                continue;
+            }
+
+            if !chosen.contains(spell_id) {
+                chosen.push(*spell_id);
+            }
+        }
+
+        if chosen.len() < 2 {
+            return current;
+        }
+
+        let mut selection = MySelection {
+            selected_skin_id: SkinId(0),
+            spell1_id: chosen[0],
+            spell2_id: chosen[1],
+            ward_skin_id: 0,
+        };
+
+        for &(spell_id, key) in &self.keys {
+            match key {
+                SpellKey::D => selection.set_spell_first(spell_id),
+                SpellKey::F => selection.set_spell_second(spell_id),
+            }
+        }
+
+        (selection.spell1_id, selection.spell2_id)
+    }
+
+    // The selection to send when the player's spells do not match the policy.
+    pub fn apply(&self, game_mode: GameMode, player: &PlayerSelection) -> Option<MySelection> {
+        let current = (player.spell1_id, player.spell2_id);
+        let (spell1_id, spell2_id) =
+            self.spells_for(game_mode, player.position(), player.champion_id, current);
+
+        if (spell1_id, spell2_id) == current {
+            return None;
+        }
+
+        Some(MySelection {
+            spell1_id,
+            spell2_id,
+            ..MySelection::from(player)
+        })
+    }
+
+    /*
+        Apply the policy whenever champ select starts or the local player's champion changes, until the
+        websocket closes. Failed requests are logged and tried again on the next session update.
+    */
+    pub async fn run(&self, driver: &LcuDriver<Initialized>) -> Result<()> {
+        let mut run = PolicyRun {
+            policy: self,
+            champion_id: None,
+            game_mode: None,
+        };
+
+        watch_uri(driver, CHAMP_SELECT_SESSION_URI, &mut run).await
+    }
+
+    // Run on a background task, stop it by aborting the returned handle.
+    pub fn spawn(self, driver: Arc<LcuDriver<Initialized>>) -> JoinHandle<Result<()>> {
+        tokio::spawn(async move { self.run(&driver).await })
+    }
+}
+
+// What `run` remembers about the current champ select.
+struct PolicyRun<'a> {
+    policy: &'a SpellPolicy,
+    champion_id: Option<ChampionId>,
+    game_mode: Option<GameMode>,
+}
+
+impl PolicyRun<'_> {
+    async fn apply(
+        &mut self,
+        driver: &LcuDriver<Initialized>,
+        player: &PlayerSelection,
+    ) -> Result<()> {
+        let game_mode = match self.game_mode {
+            Some(game_mode) => game_mode,
+            None => {
+                let game_mode = driver.get_gameflow_session().await?.map.game_mode;
+                self.game_mode = Some(game_mode);
+                game_mode
+            }
+        };
+
+        if let Some(selection) = self.policy.apply(game_mode, player) {
+            log_info!(
+                spell1_id = %selection.spell1_id,
+                spell2_id = %selection.spell2_id,
+                "Setting summoner spells"
+            );
+
+            driver.set_session_my_selection(&selection).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl UriHandler for PolicyRun<'_> {
+    type Data = ChampSelectSession;
+
+    async fn handle(
+        &mut self,
+        driver: &LcuDriver<Initialized>,
+        session: Option<ChampSelectSession>,
+    ) -> Option<Instant> {
+        // Outside of champ select reading it fails
+        let session = match session {
+            Some(session) => session,
+            None => driver.get_champ_select_session().await.ok()?,
+        };
+
+        let player = session.local_player_selection()?;
+
+        if self.champion_id == Some(player.champion_id) {
+            return None;
+        }
+
+        self.champion_id = Some(player.champion_id);
+
+        #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+        if let Err(e) = self.apply(driver, player).await {
+            log_warn!(error = %e, "Failed to set summoner spells");
+
+            // Try again with the next session
+            self.champion_id = None;
+        }
+
+        None
+    }
+
+    fn reset(&mut self) {
+        self.champion_id = None;
+        self.game_mode = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::endpoints::champ_select::summoner_spells::{
+        EXHAUST, FLASH, IGNITE, MARK, SMITE, TELEPORT,
+    };
+    use crate::endpoints::gameflow::GameMode;
+    use crate::endpoints::ids::ChampionId;
+    use crate::endpoints::lobby::LobbyPosition;
+    use crate::spell_policy::{SpellKey, SpellPolicy};
+
+    fn policy() -> SpellPolicy {
+        SpellPolicy::new()
+            .defaults(vec![FLASH, IGNITE])
+            .position(LobbyPosition::Jungle, vec![SMITE])
+            .position(LobbyPosition::Top, vec![TELEPORT])
+            .mode(GameMode::Aram, vec![MARK])
+            .champion(ChampionId(103), vec![SMITE, EXHAUST])
+            .key(FLASH, SpellKey::D)
+    }
+
+    #[test]
+    fn test_position_preference_fills_one_slot() {
+        assert_eq!(
+            policy().spells_for(
+                GameMode::Classic,
+                LobbyPosition::Jungle,
+                ChampionId(64),
+                (IGNITE, TELEPORT)
+            ),
+            (FLASH, SMITE)
+        );
+        assert_eq!(
+            policy().spells_for(
+                GameMode::Classic,
+                LobbyPosition::Top,
+                ChampionId(0),
+                (IGNITE, TELEPORT)
+            ),
+            (FLASH, TELEPORT)
+        );
+    }
+
+    #[test]
+    fn test_disallowed_spells_are_skipped() {
+        // Smite is not allowed in ARAM, so the champion preference only contributes Exhaust
+        assert_eq!(
+            policy().spells_for(
+                GameMode::Aram,
+                LobbyPosition::Unselected,
+                ChampionId(103),
+                (FLASH, MARK)
+            ),
+            (EXHAUST, MARK)
+        );
+        assert_eq!(
+            policy().spells_for(
+                GameMode::Aram,
+                LobbyPosition::Unselected,
+                ChampionId(22),
+                (FLASH, MARK)
+            ),
+            (FLASH, MARK)
+        );
+    }
+
+    #[test]
+    fn test_unfilled_slots_keep_current_spells() {
+        let policy = SpellPolicy::new().key(FLASH, SpellKey::F);
+
+        assert_eq!(
+            policy.spells_for(
+                GameMode::Classic,
+                LobbyPosition::Middle,
+                ChampionId(1),
+                (FLASH, IGNITE)
+            ),
+            (IGNITE, FLASH)
+        );
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod driver_tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use reqwest::Method;
+
+    use crate::endpoints::champ_select::summoner_spells::{FLASH, IGNITE, MARK};
+    use crate::endpoints::fixtures::load_fixture;
+    use crate::endpoints::gameflow::GameMode;
+    use crate::spell_policy::{SpellKey, SpellPolicy};
+    use crate::testing::{FakeTransport, MockResponse, RecordedRequest};
+    use crate::websocket::LcuEventType;
+
+    async fn wait_for_patch(transport: &FakeTransport) -> RecordedRequest {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Some(patch) = transport
+                    .requests()
+                    .into_iter()
+                    .find(|r| r.method == Method::PATCH)
+                {
+                    return patch;
+                }
+
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("Spells were not set")
+    }
+
+    const MY_SELECTION_BODY: &str =
+        r#"{"selectedSkinId":103000,"spell1Id":32,"spell2Id":4,"wardSkinId":-1}"#;
+
+    fn transport(gameflow_response: MockResponse) -> FakeTransport {
+        let transport = FakeTransport::new();
+
+        transport.route(
+            Method::GET,
+            "/lol-champ-select/v1/session",
+            MockResponse::json(&load_fixture("champ_select/aram.json")),
+        );
+        transport.route(Method::GET, "/lol-gameflow/v1/session", gameflow_response);
+        transport.route(
+            Method::PATCH,
+            "/lol-champ-select/v1/session/my-selection",
+            MockResponse::no_content(),
+        );
+
+        transport
+    }
+
+    fn policy() -> SpellPolicy {
+        SpellPolicy::new()
+            .defaults(vec![FLASH, IGNITE])
+            .mode(GameMode::Aram, vec![MARK])
+            .key(FLASH, SpellKey::F)
+    }
+
+    #[tokio::test]
+    async fn test_applies_policy_when_champ_select_starts() {
+        let transport = transport(MockResponse::json(&load_fixture("gameflow/aram.json")));
+        let driver = transport
+            .driver_builder("./test_data/lockfile")
+            .connect()
+            .await
+            .unwrap();
+
+        let handle = policy().spawn(Arc::new(driver));
+
+        let patch = wait_for_patch(&transport).await;
+        handle.abort();
+
+        assert_eq!(patch.body.as_deref(), Some(MY_SELECTION_BODY));
+    }
+
+    #[tokio::test]
+    async fn test_keeps_running_after_failed_request() {
+        let transport = transport(MockResponse::error(500, "Internal error"));
+        let driver = transport
+            .driver_builder("./test_data/lockfile")
+            .connect()
+            .await
+            .unwrap();
+
+        let handle = policy().spawn(Arc::new(driver));
+
+        transport
+            .wait_for_subscription("OnJsonApiEvent_lol-champ-select_v1_session")
+            .await;
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !transport
+                .requests()
+                .iter()
+                .any(|r| r.path == "/lol-gameflow/v1/session")
+            {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("Game flow session was not read");
+
+        transport.route(
+            Method::GET,
+            "/lol-gameflow/v1/session",
+            MockResponse::json(&load_fixture("gameflow/aram.json")),
+        );
+        transport.push_event(
+            "/lol-champ-select/v1/session",
+            LcuEventType::Update,
+            &load_fixture("champ_select/aram.json"),
+        );
+
+        let patch = wait_for_patch(&transport).await;
+
+        assert!(!handle.is_finished());
+        handle.abort();
+
+        assert_eq!(patch.body.as_deref(), Some(MY_SELECTION_BODY));
+    }
+}
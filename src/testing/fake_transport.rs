@@ -0,0 +1,319 @@
+use std::collections::HashSet;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use futures_util::future::BoxFuture;
+use futures_util::{Sink, Stream};
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode};
+use serde::Serialize;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::builder::LcuDriverBuilder;
+use crate::cassette::Cassette;
+use crate::errors::LcuDriverError;
+use crate::testing::{replay_events, MockResponse, RecordedRequest, Routes};
+use crate::transport::{
+    EventChannel, EventTransport, HttpTransport, TransportRequest, TransportResponse,
+};
+use crate::websocket::{LcuEvent, LcuEventType, LcuWebSocket, ALL_JSON_API_EVENTS};
+use crate::Result;
+
+/*
+    An in-memory transport that answers scripted routes and delivers pushed events without any
+    networking. Unlike `MockLcu` subscriptions take effect immediately, so events can be pushed as
+    soon as `LcuWebSocket::subscribe` returns. Clones share the same routes and channels.
+*/
+#[derive(Clone, Default)]
+pub struct FakeTransport {
+    routes: Arc<Routes>,
+    channels: Arc<Mutex<Vec<FakeChannelHandle>>>,
+}
+
+struct FakeChannelHandle {
+    subscriptions: Arc<Mutex<HashSet<String>>>,
+    sender: mpsc::UnboundedSender<Message>,
+}
+
+struct FakeEventChannel {
+    subscriptions: Arc<Mutex<HashSet<String>>>,
+    receiver: mpsc::UnboundedReceiver<Message>,
+}
+
+impl FakeTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /*
+        A driver builder that sends everything through this transport. The lockfile is only read
+        for the port and token, so any valid lockfile will do.
+    */
+    pub fn driver_builder<P: Into<std::path::PathBuf>>(
+        &self,
+        lockfile_path: P,
+    ) -> LcuDriverBuilder {
+        LcuDriverBuilder::new()
+            .lockfile_path(lockfile_path)
+            .http_transport(self.clone())
+            .event_transport(self.clone())
+    }
+
+    // Answer requests for `method` and `path` with `response`, replacing any previous route.
+    pub fn route(&self, method: Method, path: &str, response: MockResponse) {
+        self.routes.route(method, path, response);
+    }
+
+    // Answer successive requests with each of `responses` in turn, repeating the last one.
+    pub fn route_sequence(&self, method: Method, path: &str, responses: Vec<MockResponse>) {
+        self.routes.route_sequence(method, path, responses);
+    }
+
+    pub fn remove_route(&self, method: Method, path: &str) {
+        self.routes.remove_route(method, path);
+    }
+
+    // Script the routes from the HTTP responses recorded in a cassette.
+    pub fn load_cassette(&self, cassette: &Cassette) {
+        self.routes.load_cassette(cassette);
+    }
+
+    // Every request the transport has received so far, in order.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.routes.requests()
+    }
+
+    // Publish an event to every open channel that is subscribed to it.
+    pub fn push_event<T: Serialize>(&self, uri: &str, event_type: LcuEventType, data: &T) {
+        self.publish(&LcuEvent {
+            data: serde_json::to_value(data).expect("Failed to serialize fake event"),
+            event_type,
+            uri: uri.to_owned(),
+        });
+    }
+
+    // Wait until an open channel has subscribed to `topic`.
+    pub async fn wait_for_subscription(&self, topic: &str) {
+        loop {
+            let subscribed = self
+                .channels
+                .lock()
+                .expect("Fake channels lock poisoned")
+                .iter()
+                .any(|channel| {
+                    channel
+                        .subscriptions
+                        .lock()
+                        .expect("Fake subscriptions lock poisoned")
+                        .contains(topic)
+                });
+
+            if subscribed {
+                return;
+            }
+
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+    }
+
+    // Close every open event channel, which ends the websockets reading from them.
+    pub fn close_event_channels(&self) {
+        self.channels
+            .lock()
+            .expect("Fake channels lock poisoned")
+            .clear();
+    }
+
+    // Push the events recorded in a cassette, see `MockLcu::replay_events`.
+    pub async fn replay_events(&self, cassette: &Cassette, realtime: bool) {
+        replay_events(cassette, realtime, |event| self.publish(event)).await;
+    }
+
+    fn publish(&self, event: &LcuEvent) {
+        let topic = LcuWebSocket::uri_topic(&event.uri);
+
+        let mut channels = self.channels.lock().expect("Fake channels lock poisoned");
+
+        // Channels whose receiving end has been dropped are removed, subscribed or not
+        channels.retain(|channel| {
+            if channel.sender.is_closed() {
+                return false;
+            }
+
+            let subscriptions = channel
+                .subscriptions
+                .lock()
+                .expect("Fake subscriptions lock poisoned");
+
+            for subscription in [ALL_JSON_API_EVENTS, topic.as_str()] {
+                if !subscriptions.contains(subscription) {
+                    continue;
+                }
+
+                if let Ok(text) = serde_json::to_string(&(8, subscription, event)) {
+                    let _ = channel.sender.send(Message::Text(text));
+                }
+            }
+
+            true
+        });
+    }
+}
+
+impl HttpTransport for FakeTransport {
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse>> {
+        let path = match request.url.query() {
+            Some(query) => format!("{}?{}", request.url.path(), query),
+            None => request.url.path().to_owned(),
+        };
+
+        let response = self.routes.respond(request.method, &path, request.body);
+
+        Box::pin(async move {
+            let status = StatusCode::from_u16(response.status)
+                .map_err(|e| LcuDriverError::new(e.to_string()))?;
+
+            Ok(TransportResponse::from_text(status, response.body))
+        })
+    }
+}
+
+impl EventTransport for FakeTransport {
+    fn connect(
+        &self,
+        _url: url::Url,
+        _headers: HeaderMap,
+    ) -> BoxFuture<'_, Result<Box<dyn EventChannel>>> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let subscriptions = Arc::new(Mutex::new(HashSet::new()));
+
+        self.channels
+            .lock()
+            .expect("Fake channels lock poisoned")
+            .push(FakeChannelHandle {
+                subscriptions: subscriptions.clone(),
+                sender,
+            });
+
+        let channel = FakeEventChannel {
+            subscriptions,
+            receiver,
+        };
+
+        Box::pin(async move { Ok(Box::new(channel) as Box<dyn EventChannel>) })
+    }
+}
+
+impl Stream for FakeEventChannel {
+    type Item = Result<Message>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx).map(|message| message.map(Ok))
+    }
+}
+
+impl Sink<Message> for FakeEventChannel {
+    type Error = LcuDriverError;
+
+    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, message: Message) -> Result<()> {
+        if let Message::Text(text) = message {
+            let (message_type, topic) = serde_json::from_str::<(u8, String)>(&text)?;
+
+            let mut subscriptions = self
+                .subscriptions
+                .lock()
+                .expect("Fake subscriptions lock poisoned");
+
+            match message_type {
+                5 => {
+                    subscriptions.insert(topic);
+                }
+                6 => {
+                    subscriptions.remove(&topic);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.receiver.close();
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Method;
+    use serde_json::json;
+
+    use crate::testing::{FakeTransport, MockResponse};
+    use crate::websocket::LcuEventType;
+
+    #[tokio::test]
+    async fn test_driver_with_fake_transport() {
+        let transport = FakeTransport::new();
+
+        transport.route(
+            Method::GET,
+            "/lol-perks/v1/inventory",
+            MockResponse::json(&json!({ "ownedPageCount": 7 })),
+        );
+
+        let driver = transport
+            .driver_builder("./test_data/lockfile")
+            .connect()
+            .await
+            .expect("Failed to connect through fake transport");
+
+        let inventory = driver
+            .get_perks_inventory()
+            .await
+            .expect("Failed to get perks inventory");
+
+        assert_eq!(inventory.owned_page_count, 7);
+
+        let mut websocket = driver
+            .connect_websocket()
+            .await
+            .expect("Failed to connect websocket");
+
+        websocket
+            .subscribe_uri("/lol-perks/v1/inventory")
+            .await
+            .expect("Failed to subscribe");
+
+        transport.push_event(
+            "/lol-gameflow/v1/gameflow-phase",
+            LcuEventType::Update,
+            &"Lobby",
+        );
+        transport.push_event(
+            "/lol-perks/v1/inventory",
+            LcuEventType::Update,
+            &json!({ "ownedPageCount": 8 }),
+        );
+
+        let event = websocket
+            .next_event()
+            .await
+            .expect("Channel closed")
+            .expect("Failed to read event");
+
+        assert_eq!(event.uri, "/lol-perks/v1/inventory");
+        assert_eq!(transport.requests().len(), 1);
+    }
+}
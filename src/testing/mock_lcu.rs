@@ -0,0 +1,675 @@
+use std::collections::{HashMap, HashSet};
+use std::io::Cursor;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use reqwest::{Method, StatusCode};
+use rustls::{Certificate, PrivateKey, ServerConfig};
+use serde::Serialize;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tokio_rustls::TlsAcceptor;
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::Role;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+
+use crate::builder::LcuDriverBuilder;
+use crate::cassette::Cassette;
+use crate::errors::LcuDriverError;
+use crate::testing::{replay_events, MockResponse, RecordedRequest, Routes};
+use crate::websocket::{LcuEvent, LcuEventType, LcuWebSocket, ALL_JSON_API_EVENTS};
+use crate::Result;
+
+const MOCK_ROOT_CA: &[u8] = include_bytes!("../../certs/mock/mock_root_ca.pem");
+const MOCK_CERT: &[u8] = include_bytes!("../../certs/mock/mock_lcu.pem");
+const MOCK_KEY: &[u8] = include_bytes!("../../certs/mock/mock_lcu.key");
+
+const MOCK_PASSWORD: &str = "mock-lcu-password";
+
+/*
+    An in-process stand-in for the League client. It serves HTTPS on a random local port using a
+    certificate signed by a bundled mock root CA, writes a matching lockfile into a temporary install
+    directory, answers scripted REST routes and pushes WAMP events to connected websockets. Use
+    `driver_builder` to get an `LcuDriverBuilder` that connects to it.
+*/
+pub struct MockLcu {
+    addr: SocketAddr,
+    install_directory: PathBuf,
+    state: Arc<MockState>,
+    events: broadcast::Sender<LcuEvent>,
+    server: JoinHandle<()>,
+}
+
+#[derive(Default)]
+struct MockState {
+    routes: Routes,
+    subscriptions: Mutex<Vec<String>>,
+}
+
+impl MockLcu {
+    pub async fn start() -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+
+        let install_directory =
+            std::env::temp_dir().join(format!("lcu_driver_mock_{}", addr.port()));
+        tokio::fs::create_dir_all(&install_directory).await?;
+
+        let lockfile = format!(
+            "LeagueClient:{}:{}:{}:https",
+            std::process::id(),
+            addr.port(),
+            MOCK_PASSWORD
+        );
+        tokio::fs::write(install_directory.join("lockfile"), lockfile).await?;
+
+        let acceptor = TlsAcceptor::from(Arc::new(Self::server_config()?));
+        let state = Arc::new(MockState::default());
+        let (events, _) = broadcast::channel(64);
+
+        let server = tokio::spawn(Self::serve(
+            listener,
+            acceptor,
+            state.clone(),
+            events.clone(),
+        ));
+
+        Ok(Self {
+            addr,
+            install_directory,
+            state,
+            events,
+            server,
+        })
+    }
+
+    fn server_config() -> Result<ServerConfig> {
+        let certs = rustls_pemfile::certs(&mut Cursor::new(MOCK_CERT))?
+            .into_iter()
+            .map(Certificate)
+            .collect();
+
+        let key = rustls_pemfile::pkcs8_private_keys(&mut Cursor::new(MOCK_KEY))?
+            .pop()
+            .map(PrivateKey)
+            .ok_or(LcuDriverError::FailedToReadCertificate)?;
+
+        ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .map_err(|_| LcuDriverError::FailedToReadCertificate)
+    }
+
+    pub fn port(&self) -> u16 {
+        self.addr.port()
+    }
+
+    pub fn install_directory(&self) -> &Path {
+        &self.install_directory
+    }
+
+    pub fn lockfile_path(&self) -> PathBuf {
+        self.install_directory.join("lockfile")
+    }
+
+    // A driver builder that trusts the mock certificate and reads the mock lockfile.
+    pub fn driver_builder(&self) -> LcuDriverBuilder {
+        LcuDriverBuilder::new()
+            .root_certificate(MOCK_ROOT_CA.to_vec())
+            .lockfile_path(self.lockfile_path())
+    }
+
+    // Answer requests for `method` and `path` with `response`, replacing any previous route.
+    pub fn route(&self, method: Method, path: &str, response: MockResponse) {
+        self.state.routes.route(method, path, response);
+    }
+
+    /*
+        Answer successive requests for `method` and `path` with each of `responses` in turn. Once
+        only the last response remains it is returned for every further request.
+    */
+    pub fn route_sequence(&self, method: Method, path: &str, responses: Vec<MockResponse>) {
+        self.state.routes.route_sequence(method, path, responses);
+    }
+
+    pub fn remove_route(&self, method: Method, path: &str) {
+        self.state.routes.remove_route(method, path);
+    }
+
+    // Script the routes from the HTTP responses recorded in a cassette.
+    pub fn load_cassette(&self, cassette: &Cassette) {
+        self.state.routes.load_cassette(cassette);
+    }
+
+    // Every request the mock has received so far, in order.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.routes.requests()
+    }
+
+    // Publish an event to every connected websocket that is subscribed to it.
+    pub fn push_event<T: Serialize>(&self, uri: &str, event_type: LcuEventType, data: &T) {
+        let _ = self.events.send(LcuEvent {
+            data: serde_json::to_value(data).expect("Failed to serialize mock event"),
+            event_type,
+            uri: uri.to_owned(),
+        });
+    }
+
+    /*
+        Wait until a websocket has subscribed to `topic`. Events pushed before the subscription has
+        been received by the mock are not delivered, so tests should wait before pushing.
+    */
+    pub async fn wait_for_subscription(&self, topic: &str) {
+        loop {
+            if self
+                .state
+                .subscriptions
+                .lock()
+                .expect("Mock subscriptions lock poisoned")
+                .iter()
+                .any(|t| t == topic)
+            {
+                return;
+            }
+
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    /*
+        Push the events recorded in a cassette to connected websockets. When `realtime` is set the
+        recorded gaps between events are reproduced, otherwise events are pushed back to back.
+    */
+    pub async fn replay_events(&self, cassette: &Cassette, realtime: bool) {
+        replay_events(cassette, realtime, |event| {
+            let _ = self.events.send(event.clone());
+        })
+        .await;
+    }
+
+    async fn serve(
+        listener: TcpListener,
+        acceptor: TlsAcceptor,
+        state: Arc<MockState>,
+        events: broadcast::Sender<LcuEvent>,
+    ) {
+        while let Ok((socket, _)) = listener.accept().await {
+            let acceptor = acceptor.clone();
+            let state = state.clone();
+            let events = events.subscribe();
+
+            tokio::spawn(async move {
+                if let Ok(stream) = acceptor.accept(socket).await {
+                    let _ = Self::handle_connection(stream, state, events).await;
+                }
+            });
+        }
+    }
+
+    async fn handle_connection<S>(
+        stream: S,
+        state: Arc<MockState>,
+        events: broadcast::Receiver<LcuEvent>,
+    ) -> Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let mut reader = BufReader::new(stream);
+
+        // Connections are kept alive, so keep reading requests until the client hangs up
+        while let Some(request) = Self::read_request(&mut reader).await? {
+            if let Some(key) = request.headers.get("sec-websocket-key") {
+                let accept = derive_accept_key(key.as_bytes());
+
+                let mut stream = reader.into_inner();
+                stream
+                    .write_all(
+                        format!(
+                            "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+                            accept
+                        )
+                        .as_bytes(),
+                    )
+                    .await?;
+
+                let websocket = WebSocketStream::from_raw_socket(stream, Role::Server, None).await;
+
+                return Self::handle_websocket(websocket, &state, events).await;
+            }
+
+            let response = Self::respond(&state, request);
+
+            let reason = StatusCode::from_u16(response.status)
+                .ok()
+                .and_then(|s| s.canonical_reason())
+                .unwrap_or("Unknown");
+
+            let head = format!(
+                "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+                response.status,
+                reason,
+                response.body.len()
+            );
+
+            let stream = reader.get_mut();
+            stream.write_all(head.as_bytes()).await?;
+            stream.write_all(response.body.as_bytes()).await?;
+            stream.flush().await?;
+        }
+
+        Ok(())
+    }
+
+    fn respond(state: &MockState, request: MockHttpRequest) -> MockResponse {
+        let authorized = request.headers.get("authorization")
+            == Some(&format!(
+                "Basic {}",
+                base64::encode(format!("riot:{}", MOCK_PASSWORD))
+            ));
+
+        if !authorized {
+            return MockResponse::error(401, "Unauthorized");
+        }
+
+        state
+            .routes
+            .respond(request.method, &request.path, request.body)
+    }
+
+    async fn read_request<R>(reader: &mut R) -> Result<Option<MockHttpRequest>>
+    where
+        R: AsyncBufReadExt + Unpin,
+    {
+        let mut request_line = String::new();
+
+        if reader.read_line(&mut request_line).await? == 0 {
+            return Ok(None);
+        }
+
+        let mut parts = request_line.split_whitespace();
+
+        let method = parts
+            .next()
+            .and_then(|m| Method::from_bytes(m.as_bytes()).ok())
+            .ok_or_else(|| LcuDriverError::new("Mock received an invalid request line"))?;
+
+        let path = parts
+            .next()
+            .map(|p| p.split('?').next().unwrap_or(p).to_owned())
+            .ok_or_else(|| LcuDriverError::new("Mock received an invalid request line"))?;
+
+        let mut headers = HashMap::new();
+
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).await?;
+
+            let line = line.trim_end();
+
+            if line.is_empty() {
+                break;
+            }
+
+            if let Some((name, value)) = line.split_once(':') {
+                headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_owned());
+            }
+        }
+
+        let content_length = headers
+            .get("content-length")
+            .and_then(|l| l.parse::<usize>().ok())
+            .unwrap_or(0);
+
+        let body = if content_length > 0 {
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).await?;
+
+            Some(String::from_utf8(body)?)
+        } else {
+            None
+        };
+
+        Ok(Some(MockHttpRequest {
+            method,
+            path,
+            headers,
+            body,
+        }))
+    }
+
+    async fn handle_websocket<S>(
+        mut websocket: WebSocketStream<S>,
+        state: &MockState,
+        mut events: broadcast::Receiver<LcuEvent>,
+    ) -> Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let mut subscriptions = HashSet::new();
+
+        let result =
+            Self::websocket_loop(&mut websocket, state, &mut events, &mut subscriptions).await;
+
+        let mut all_subscriptions = state
+            .subscriptions
+            .lock()
+            .expect("Mock subscriptions lock poisoned");
+
+        for topic in subscriptions {
+            if let Some(index) = all_subscriptions.iter().position(|t| *t == topic) {
+                all_subscriptions.remove(index);
+            }
+        }
+
+        result
+    }
+
+    async fn websocket_loop<S>(
+        websocket: &mut WebSocketStream<S>,
+        state: &MockState,
+        events: &mut broadcast::Receiver<LcuEvent>,
+        subscriptions: &mut HashSet<String>,
+    ) -> Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        loop {
+            tokio::select! {
+                message = websocket.next() => {
+                    let text = match message {
+                        Some(Ok(Message::Text(text))) => text,
+                        Some(Ok(Message::Close(_))) | None => return Ok(()),
+                        Some(Ok(_)) => continue,
+                        Some(Err(e)) => return Err(e.into()),
+                    };
+
+                    let (message_type, topic) = match serde_json::from_str::<(u8, String)>(&text) {
+                        Ok(message) => message,
+                        Err(_) => continue,
+                    };
+
+                    let mut all_subscriptions = state
+                        .subscriptions
+                        .lock()
+                        .expect("Mock subscriptions lock poisoned");
+
+                    match message_type {
+                        5 if subscriptions.insert(topic.clone()) => {
+                            all_subscriptions.push(topic);
+                        }
+                        6 if subscriptions.remove(&topic) => {
+                            if let Some(index) = all_subscriptions.iter().position(|t| *t == topic) {
+                                all_subscriptions.remove(index);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                event = events.recv() => {
+                    let event = match event {
+                        Ok(event) => event,
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => return Ok(()),
+                    };
+
+                    let topic = LcuWebSocket::uri_topic(&event.uri);
+
+                    for subscription in [ALL_JSON_API_EVENTS, topic.as_str()] {
+                        if subscriptions.contains(subscription) {
+                            let message: (u8, &str, &LcuEvent) = (8, subscription, &event);
+
+                            websocket
+                                .send(Message::Text(serde_json::to_string(&message)?))
+                                .await?;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Drop for MockLcu {
+    fn drop(&mut self) {
+        self.server.abort();
+
+        let _ = std::fs::remove_dir_all(&self.install_directory);
+    }
+}
+
+struct MockHttpRequest {
+    method: Method,
+    path: String,
+    headers: HashMap<String, String>,
+    body: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use reqwest::Method;
+    use serde_json::json;
+
+    use crate::cassette::{Cassette, CassetteRecorder};
+    use crate::errors::LcuDriverError;
+    use crate::models::api_error::LcuApiError;
+    use crate::testing::{MockLcu, MockResponse};
+    use crate::websocket::LcuEventType;
+
+    fn current_summoner() -> serde_json::Value {
+        json!({
+            "accountId": 203350823,
+            "displayName": "Mock Summoner",
+            "internalName": "MockSummoner",
+            "nameChangeFlag": false,
+            "percentCompleteForNextLevel": 42,
+            "profileIconId": 4568,
+            "puuid": "7f3a3f1c-2b8f-5c4e-9f41-6b2f1a3d9c10",
+            "rerollPoints": {
+                "currentPoints": 250,
+                "maxRolls": 2,
+                "numberOfRolls": 0,
+                "pointsCostToRoll": 250,
+                "pointsToReroll": 0
+            },
+            "summonerId": 58291734,
+            "summonerLevel": 187,
+            "unnamed": false,
+            "xpSinceLastLevel": 1210,
+            "xpUntilNextLevel": 2880
+        })
+    }
+
+    #[tokio::test]
+    async fn test_driver_against_mock() {
+        let mock = MockLcu::start().await.expect("Failed to start mock LCU");
+
+        mock.route(
+            Method::GET,
+            "/lol-summoner/v1/current-summoner",
+            MockResponse::json(&current_summoner()),
+        );
+
+        let driver = mock
+            .driver_builder()
+            .connect()
+            .await
+            .expect("Failed to connect to mock LCU");
+
+        let summoner = driver
+            .get_current_summoner()
+            .await
+            .expect("Failed to get current summoner");
+
+        assert_eq!(summoner.display_name, "Mock Summoner");
+        assert_eq!(driver.league_install_dir().await, mock.install_directory());
+
+        let err = driver
+            .get_gameflow_session()
+            .await
+            .expect_err("Unrouted endpoints should fail");
+
+        assert_eq!(
+            err,
+            LcuDriverError::ApiError(LcuApiError::UnknownError(
+                "No mock route for /lol-gameflow/v1/session".to_owned()
+            ))
+        );
+
+        assert_eq!(mock.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_websocket_events_from_mock() {
+        let mock = MockLcu::start().await.expect("Failed to start mock LCU");
+
+        let driver = mock
+            .driver_builder()
+            .connect()
+            .await
+            .expect("Failed to connect to mock LCU");
+
+        let mut websocket = driver
+            .connect_websocket()
+            .await
+            .expect("Failed to connect websocket");
+
+        websocket
+            .subscribe_uri("/lol-gameflow/v1/gameflow-phase")
+            .await
+            .expect("Failed to subscribe");
+
+        mock.wait_for_subscription("OnJsonApiEvent_lol-gameflow_v1_gameflow-phase")
+            .await;
+
+        mock.push_event("/lol-gameflow/v1/session", LcuEventType::Update, &json!({}));
+        mock.push_event(
+            "/lol-gameflow/v1/gameflow-phase",
+            LcuEventType::Update,
+            &"ChampSelect",
+        );
+
+        let event = websocket
+            .next_event()
+            .await
+            .expect("Websocket closed")
+            .expect("Failed to read event");
+
+        assert_eq!(event.uri, "/lol-gameflow/v1/gameflow-phase");
+        assert_eq!(event.data, json!("ChampSelect"));
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay_cassette() {
+        let mock = MockLcu::start().await.expect("Failed to start mock LCU");
+
+        mock.route_sequence(
+            Method::GET,
+            "/lol-summoner/v1/current-summoner",
+            vec![
+                MockResponse::error(503, "Summoner not ready"),
+                MockResponse::json(&current_summoner()),
+            ],
+        );
+
+        let cassette_path = mock.install_directory().join("session.jsonl");
+
+        let recorder = Arc::new(
+            CassetteRecorder::create(&cassette_path)
+                .await
+                .expect("Failed to create cassette recorder"),
+        );
+
+        let driver = mock
+            .driver_builder()
+            .interceptor(recorder.clone())
+            .connect()
+            .await
+            .expect("Failed to connect to mock LCU");
+
+        let mut websocket = driver
+            .connect_websocket()
+            .await
+            .expect("Failed to connect websocket");
+
+        websocket
+            .subscribe("OnJsonApiEvent")
+            .await
+            .expect("Failed to subscribe");
+
+        mock.wait_for_subscription("OnJsonApiEvent").await;
+
+        assert!(driver.get_current_summoner().await.is_err());
+        assert!(driver.get_current_summoner().await.is_ok());
+
+        mock.push_event(
+            "/lol-gameflow/v1/gameflow-phase",
+            LcuEventType::Update,
+            &"Lobby",
+        );
+
+        websocket
+            .next_event()
+            .await
+            .expect("Websocket closed")
+            .expect("Failed to read event");
+
+        recorder.flush().await.expect("Failed to flush cassette");
+
+        let cassette = Cassette::load(&cassette_path)
+            .await
+            .expect("Failed to load cassette");
+
+        assert_eq!(cassette.entries.len(), 3);
+
+        // Replay the recording against a fresh mock
+        let replay = MockLcu::start().await.expect("Failed to start mock LCU");
+        replay.load_cassette(&cassette);
+
+        let driver = replay
+            .driver_builder()
+            .connect()
+            .await
+            .expect("Failed to connect to mock LCU");
+
+        let mut websocket = driver
+            .connect_websocket()
+            .await
+            .expect("Failed to connect websocket");
+
+        websocket
+            .subscribe("OnJsonApiEvent")
+            .await
+            .expect("Failed to subscribe");
+
+        replay.wait_for_subscription("OnJsonApiEvent").await;
+
+        assert!(driver.get_current_summoner().await.is_err());
+        assert_eq!(
+            driver
+                .get_current_summoner()
+                .await
+                .expect("Failed to get current summoner")
+                .display_name,
+            "Mock Summoner"
+        );
+
+        replay.replay_events(&cassette, false).await;
+
+        let event = websocket
+            .next_event()
+            .await
+            .expect("Websocket closed")
+            .expect("Failed to read event");
+
+        assert_eq!(event.data, json!("Lobby"));
+    }
+}
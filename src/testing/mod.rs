@@ -0,0 +1,176 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use reqwest::Method;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::cassette::{Cassette, CassetteEntry};
+use crate::websocket::LcuEvent;
+
+pub use fake_transport::FakeTransport;
+pub use mock_lcu::MockLcu;
+
+mod fake_transport;
+mod mock_lcu;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MockResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl MockResponse {
+    pub fn json<T: Serialize>(value: &T) -> Self {
+        Self {
+            status: 200,
+            body: serde_json::to_string(value).expect("Failed to serialize mock response"),
+        }
+    }
+
+    pub fn no_content() -> Self {
+        Self {
+            status: 204,
+            body: String::new(),
+        }
+    }
+
+    // An error in the same shape the client uses, so that it is surfaced as an `ApiError`.
+    pub fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: json!({
+                "errorCode": "RPC_ERROR",
+                "httpStatus": status,
+                "message": message,
+            })
+            .to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RecordedRequest {
+    pub method: Method,
+    pub path: String,
+    pub body: Option<String>,
+}
+
+// Scripted REST routes shared by `MockLcu` and `FakeTransport`.
+#[derive(Debug, Default)]
+struct Routes {
+    routes: Mutex<HashMap<(Method, String), VecDeque<MockResponse>>>,
+    requests: Mutex<Vec<RecordedRequest>>,
+}
+
+impl Routes {
+    fn route(&self, method: Method, path: &str, response: MockResponse) {
+        self.route_sequence(method, path, vec![response]);
+    }
+
+    fn route_sequence(&self, method: Method, path: &str, responses: Vec<MockResponse>) {
+        self.routes
+            .lock()
+            .expect("Mock routes lock poisoned")
+            .insert((method, path.to_owned()), responses.into());
+    }
+
+    fn remove_route(&self, method: Method, path: &str) {
+        self.routes
+            .lock()
+            .expect("Mock routes lock poisoned")
+            .remove(&(method, path.to_owned()));
+    }
+
+    // Every endpoint replays the responses recorded for it in order.
+    fn load_cassette(&self, cassette: &Cassette) {
+        let mut sequences: HashMap<(Method, String), Vec<MockResponse>> = HashMap::new();
+
+        for entry in &cassette.entries {
+            if let CassetteEntry::Http {
+                method,
+                path,
+                status,
+                response_body,
+                ..
+            } = entry
+            {
+                let method = match Method::from_bytes(method.as_bytes()) {
+                    Ok(method) => method,
+                    Err(_) => continue,
+                };
+
+                sequences
+                    .entry((method, strip_query(path).to_owned()))
+                    .or_default()
+                    .push(MockResponse {
+                        status: *status,
+                        body: response_body.clone().unwrap_or_default(),
+                    });
+            }
+        }
+
+        for ((method, path), responses) in sequences {
+            self.route_sequence(method, &path, responses);
+        }
+    }
+
+    fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests
+            .lock()
+            .expect("Mock requests lock poisoned")
+            .clone()
+    }
+
+    fn respond(&self, method: Method, path: &str, body: Option<String>) -> MockResponse {
+        let path = strip_query(path);
+
+        self.requests
+            .lock()
+            .expect("Mock requests lock poisoned")
+            .push(RecordedRequest {
+                method: method.clone(),
+                path: path.to_owned(),
+                body,
+            });
+
+        let mut routes = self.routes.lock().expect("Mock routes lock poisoned");
+
+        routes
+            .get_mut(&(method, path.to_owned()))
+            .and_then(|responses| {
+                if responses.len() > 1 {
+                    responses.pop_front()
+                } else {
+                    responses.front().cloned()
+                }
+            })
+            .unwrap_or_else(|| MockResponse::error(404, &format!("No mock route for {}", path)))
+    }
+}
+
+fn strip_query(path: &str) -> &str {
+    path.split('?').next().unwrap_or(path)
+}
+
+/*
+    Hand the events recorded in a cassette to `push` in order. When `realtime` is set the recorded
+    gaps between events are reproduced, otherwise events are pushed back to back.
+*/
+async fn replay_events<F: FnMut(&LcuEvent)>(cassette: &Cassette, realtime: bool, mut push: F) {
+    let mut previous = None;
+
+    for entry in &cassette.entries {
+        if let CassetteEntry::Event { timestamp, event } = entry {
+            if let (true, Some(previous)) = (realtime, previous) {
+                let gap = timestamp.saturating_sub(previous);
+                tokio::time::sleep(Duration::from_millis(gap)).await;
+            }
+
+            previous = Some(*timestamp);
+
+            push(event);
+        }
+    }
+}
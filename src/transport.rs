@@ -0,0 +1,230 @@
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures_util::future::BoxFuture;
+use futures_util::stream::{self, BoxStream};
+use futures_util::{Sink, SinkExt, Stream, StreamExt, TryStreamExt};
+use reqwest::header::HeaderMap;
+use reqwest::{Body, Certificate as ReqwestCertificate, Client, ClientBuilder, Method, StatusCode};
+use rustls::{Certificate, ClientConfig, RootCertStore};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::errors::LcuDriverError;
+use crate::logging::log_error;
+use crate::Result;
+
+/*
+    The HTTP side of the connection to the client. The driver builds fully formed requests, including
+    the authorization header from the lockfile, so a transport only has to deliver them.
+*/
+pub trait HttpTransport: Send + Sync {
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse>>;
+}
+
+/*
+    The event side of the connection to the client. A transport opens a channel of WAMP messages,
+    which the driver wraps in an `LcuWebSocket`.
+*/
+pub trait EventTransport: Send + Sync {
+    fn connect(
+        &self,
+        url: url::Url,
+        headers: HeaderMap,
+    ) -> BoxFuture<'_, Result<Box<dyn EventChannel>>>;
+}
+
+pub trait EventChannel:
+    Stream<Item = Result<Message>> + Sink<Message, Error = LcuDriverError> + Send + Unpin
+{
+}
+
+impl<T> EventChannel for T where
+    T: Stream<Item = Result<Message>> + Sink<Message, Error = LcuDriverError> + Send + Unpin
+{
+}
+
+#[derive(Debug, Clone)]
+pub struct TransportRequest {
+    pub method: Method,
+    pub url: url::Url,
+    pub headers: HeaderMap,
+    pub body: Option<String>,
+}
+
+pub struct TransportResponse {
+    pub status: StatusCode,
+    pub content_length: Option<u64>,
+    pub body: BoxStream<'static, Result<Bytes>>,
+}
+
+impl TransportResponse {
+    pub fn from_text<S: Into<String>>(status: StatusCode, text: S) -> Self {
+        let text = Bytes::from(text.into());
+
+        Self {
+            status,
+            content_length: Some(text.len() as u64),
+            body: stream::once(async move { Ok(text) }).boxed(),
+        }
+    }
+
+    pub async fn text(self) -> Result<String> {
+        let bytes = self
+            .body
+            .try_fold(Vec::new(), |mut bytes, chunk| async move {
+                bytes.extend_from_slice(&chunk);
+                Ok(bytes)
+            })
+            .await?;
+
+        Ok(String::from_utf8(bytes)?)
+    }
+}
+
+// Parse the first certificate out of a PEM file.
+pub(crate) fn read_certificate(pem: &[u8]) -> Result<Certificate> {
+    let mut cert_raw_cursor = Cursor::new(pem);
+    let rustls_cert_raw = rustls_pemfile::read_one(&mut cert_raw_cursor)?;
+
+    match rustls_cert_raw {
+        Some(rustls_pemfile::Item::X509Certificate(bytes)) => Ok(Certificate(bytes)),
+        _ => {
+            log_error!("Did not receive a valid X509Certificate.");
+            Err(LcuDriverError::FailedToReadCertificate)
+        }
+    }
+}
+
+// The default HTTP transport, backed by reqwest.
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    // Create a transport that trusts the given PEM encoded root certificate.
+    pub fn new(root_certificate: &[u8]) -> Result<Self> {
+        let reqwest_cert = ReqwestCertificate::from_pem(root_certificate)?;
+
+        let client = ClientBuilder::new()
+            .add_root_certificate(reqwest_cert)
+            .connect_timeout(Duration::from_secs(5))
+            .timeout(Duration::from_secs(5))
+            .build()?;
+
+        Ok(Self { client })
+    }
+}
+
+impl HttpTransport for ReqwestTransport {
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse>> {
+        Box::pin(async move {
+            let mut req = reqwest::Request::new(request.method, request.url);
+
+            *req.headers_mut() = request.headers;
+
+            if let Some(body) = request.body {
+                *req.body_mut() = Some(Body::from(body));
+            }
+
+            let res = self
+                .client
+                .execute(req)
+                .await
+                .map_err(|e| LcuDriverError::FailedToSendRequest(e.to_string()))?;
+
+            Ok(TransportResponse {
+                status: res.status(),
+                content_length: res.content_length(),
+                body: res
+                    .bytes_stream()
+                    .map_err(|e| LcuDriverError::FailedToReadResponse(e.to_string()))
+                    .boxed(),
+            })
+        })
+    }
+}
+
+// The default event transport, a tokio-tungstenite websocket over rustls.
+pub struct TungsteniteTransport {
+    rustls_config: Arc<ClientConfig>,
+}
+
+impl TungsteniteTransport {
+    // Create a transport that trusts the given PEM encoded root certificate.
+    pub fn new(root_certificate: &[u8]) -> Result<Self> {
+        let rustls_cert = read_certificate(root_certificate)?;
+
+        let mut root_store = RootCertStore::empty();
+        #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+        root_store.add(&rustls_cert).map_err(|e| {
+            log_error!(error = %e, "Failed to add root certificate to the root store");
+
+            LcuDriverError::FailedToReadCertificate
+        })?;
+
+        let rustls_config = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(root_store)
+            .with_no_client_auth();
+
+        Ok(Self {
+            rustls_config: Arc::new(rustls_config),
+        })
+    }
+}
+
+impl EventTransport for TungsteniteTransport {
+    fn connect(
+        &self,
+        url: url::Url,
+        headers: HeaderMap,
+    ) -> BoxFuture<'_, Result<Box<dyn EventChannel>>> {
+        Box::pin(async move {
+            let connector = tokio_tungstenite::Connector::Rustls(self.rustls_config.clone());
+
+            let uri = url.as_str().parse::<http::Uri>()?;
+
+            // Let tungstenite generate the websocket handshake headers before adding our own
+            let mut request = uri.into_client_request()?;
+
+            request.headers_mut().extend(headers);
+
+            let port = request
+                .uri()
+                .port_u16()
+                .or_else(|| match request.uri().scheme_str() {
+                    Some("wss") => Some(443),
+                    Some("ws") => Some(80),
+                    _ => None,
+                })
+                .expect("Failed to read websocket port");
+
+            let domain = request
+                .uri()
+                .host()
+                .map(|d| d.to_string())
+                .expect("Failed to read websocket domain");
+
+            let addr = format!("{}:{}", domain, port);
+
+            let socket = tokio::net::TcpStream::connect(addr).await?;
+
+            let (websocket, _) = tokio_tungstenite::client_async_tls_with_config(
+                request,
+                socket,
+                None,
+                Some(connector),
+            )
+            .await?;
+
+            let channel = websocket
+                .map_err(LcuDriverError::from)
+                .sink_map_err(LcuDriverError::from);
+
+            Ok(Box::new(channel) as Box<dyn EventChannel>)
+        })
+    }
+}
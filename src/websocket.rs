@@ -0,0 +1,190 @@
+use std::sync::Arc;
+
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::errors::LcuDriverError;
+use crate::interceptor::Interceptor;
+use crate::logging::log_debug;
+use crate::transport::EventChannel;
+use crate::Result;
+
+// WAMP 1.0 message type IDs used by the client
+const WAMP_SUBSCRIBE: u8 = 5;
+const WAMP_UNSUBSCRIBE: u8 = 6;
+const WAMP_EVENT: u8 = 8;
+
+// Subscribing to this topic receives every JSON API event the client publishes
+pub const ALL_JSON_API_EVENTS: &str = "OnJsonApiEvent";
+
+pub struct LcuWebSocket {
+    stream: Box<dyn EventChannel>,
+    interceptors: Arc<Vec<Arc<dyn Interceptor>>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LcuEvent {
+    pub data: Value,
+    pub event_type: LcuEventType,
+    pub uri: String,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum LcuEventType {
+    Create,
+    Update,
+    Delete,
+}
+
+impl LcuWebSocket {
+    pub(crate) fn new(
+        stream: Box<dyn EventChannel>,
+        interceptors: Arc<Vec<Arc<dyn Interceptor>>>,
+    ) -> Self {
+        Self {
+            stream,
+            interceptors,
+        }
+    }
+
+    /*
+        Subscribe to a WAMP topic. Topics are either `OnJsonApiEvent` for every event, or
+        a single endpoint such as `OnJsonApiEvent_lol-gameflow_v1_gameflow-phase`.
+    */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "lcu_websocket_subscribe", skip(self), err)
+    )]
+    pub async fn subscribe(&mut self, topic: &str) -> Result<()> {
+        self.send(serde_json::to_string(&(WAMP_SUBSCRIBE, topic))?)
+            .await
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "lcu_websocket_unsubscribe", skip(self), err)
+    )]
+    pub async fn unsubscribe(&mut self, topic: &str) -> Result<()> {
+        self.send(serde_json::to_string(&(WAMP_UNSUBSCRIBE, topic))?)
+            .await
+    }
+
+    // Subscribe to the events published for a single endpoint, e.g. `/lol-gameflow/v1/gameflow-phase`.
+    pub async fn subscribe_uri(&mut self, uri: &str) -> Result<()> {
+        self.subscribe(&Self::uri_topic(uri)).await
+    }
+
+    pub fn uri_topic(uri: &str) -> String {
+        format!(
+            "{}_{}",
+            ALL_JSON_API_EVENTS,
+            uri.trim_start_matches('/').replace('/', "_")
+        )
+    }
+
+    async fn send(&mut self, text: String) -> Result<()> {
+        let message = Message::Text(text);
+
+        for interceptor in self.interceptors.iter() {
+            interceptor.on_websocket_send(&message)?;
+        }
+
+        self.stream.send(message).await?;
+
+        Ok(())
+    }
+
+    /*
+        Wait for the next JSON API event. Messages that are not events (e.g. pings) are skipped.
+        Returns `None` once the websocket has been closed.
+    */
+    pub async fn next_event(&mut self) -> Option<Result<LcuEvent>> {
+        while let Some(message) = self.stream.next().await {
+            let message = match message {
+                Ok(message) => message,
+                Err(e) => return Some(Err(e)),
+            };
+
+            for interceptor in self.interceptors.iter() {
+                interceptor.on_websocket_receive(&message);
+            }
+
+            let text = match message {
+                Message::Text(text) => text,
+                Message::Close(_) => {
+                    log_debug!("League client closed the websocket");
+
+                    return None;
+                }
+                _ => continue,
+            };
+
+            if text.is_empty() {
+                continue;
+            }
+
+            let event = Self::parse_event(&text);
+
+            #[cfg(feature = "tracing")]
+            match &event {
+                Ok(event) => {
+                    tracing::trace!(uri = %event.uri, event_type = ?event.event_type, "Received websocket event");
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to parse websocket event");
+                }
+            }
+
+            return Some(event);
+        }
+
+        None
+    }
+
+    pub(crate) fn parse_event(text: &str) -> Result<LcuEvent> {
+        let (message_type, _topic, event) = serde_json::from_str::<(u8, String, LcuEvent)>(text)?;
+
+        if message_type != WAMP_EVENT {
+            return Err(LcuDriverError::new(format!(
+                "Unexpected WAMP message type {}",
+                message_type
+            )));
+        }
+
+        Ok(event)
+    }
+
+    pub async fn close(mut self) -> Result<()> {
+        self.stream.close().await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uri_topic() {
+        assert_eq!(
+            LcuWebSocket::uri_topic("/lol-gameflow/v1/gameflow-phase"),
+            "OnJsonApiEvent_lol-gameflow_v1_gameflow-phase"
+        );
+    }
+
+    #[test]
+    fn test_parse_event() {
+        let event = LcuWebSocket::parse_event(
+            r#"[8,"OnJsonApiEvent",{"data":"ChampSelect","eventType":"Update","uri":"/lol-gameflow/v1/gameflow-phase"}]"#,
+        )
+        .expect("Failed to parse event");
+
+        assert_eq!(event.event_type, LcuEventType::Update);
+        assert_eq!(event.uri, "/lol-gameflow/v1/gameflow-phase");
+        assert_eq!(event.data, Value::String("ChampSelect".to_owned()));
+    }
+}
@@ -0,0 +1,24 @@
+// Generated by lcu_codegen from the client's API schema. Do not edit by hand.
+
+use reqwest::Method;
+
+use crate::endpoints::EndpointInfo;
+
+#[allow(unused)]
+pub enum BuiltinEndpoint<'a> {
+    // GET /{plugin}/assets/{path}
+    GetPluginAssetsByPath(&'a str, &'a str),
+}
+
+impl<'a> BuiltinEndpoint<'a> {
+    pub fn info(&self) -> EndpointInfo {
+        match self {
+            BuiltinEndpoint::GetPluginAssetsByPath(plugin, path) => EndpointInfo {
+                url: format!("/{}/assets/{}", plugin, path),
+                method: Method::GET,
+                headers: None,
+                body: None,
+            },
+        }
+    }
+}
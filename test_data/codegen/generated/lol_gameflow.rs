@@ -0,0 +1,26 @@
+// Generated by lcu_codegen from the client's API schema. Do not edit by hand.
+
+use reqwest::Method;
+
+use crate::endpoints::EndpointInfo;
+
+const LOL_GAMEFLOW_URL: &str = "/lol-gameflow";
+
+#[allow(unused)]
+pub enum LolGameflowEndpoint {
+    // GET /lol-gameflow/v1/gameflow-phase -> LolGameflowGameflowPhase
+    GetLolGameflowV1GameflowPhase,
+}
+
+impl LolGameflowEndpoint {
+    pub fn info(&self) -> EndpointInfo {
+        match self {
+            LolGameflowEndpoint::GetLolGameflowV1GameflowPhase => EndpointInfo {
+                url: format!("{}/v1/gameflow-phase", LOL_GAMEFLOW_URL),
+                method: Method::GET,
+                headers: None,
+                body: None,
+            },
+        }
+    }
+}
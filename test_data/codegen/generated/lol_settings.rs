@@ -0,0 +1,28 @@
+// Generated by lcu_codegen from the client's API schema. Do not edit by hand.
+
+use std::collections::HashMap;
+
+use reqwest::Method;
+
+use crate::endpoints::EndpointInfo;
+
+const LOL_SETTINGS_URL: &str = "/lol-settings";
+
+#[allow(unused)]
+pub enum LolSettingsEndpoint<'a> {
+    // PATCH /lol-settings/v1/account/{category}
+    PatchLolSettingsV1AccountByCategory(&'a str, &'a HashMap<String, serde_json::Value>),
+}
+
+impl<'a> LolSettingsEndpoint<'a> {
+    pub fn info(&self) -> EndpointInfo {
+        match self {
+            LolSettingsEndpoint::PatchLolSettingsV1AccountByCategory(category, body) => EndpointInfo {
+                url: format!("{}/v1/account/{}", LOL_SETTINGS_URL, category),
+                method: Method::PATCH,
+                headers: None,
+                body: serde_json::to_string(body).ok(),
+            },
+        }
+    }
+}
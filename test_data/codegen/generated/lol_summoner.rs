@@ -0,0 +1,51 @@
+// Generated by lcu_codegen from the client's API schema. Do not edit by hand.
+
+use reqwest::Method;
+
+use crate::endpoints::{url_with_query, EndpointInfo};
+use super::types::LolSummonerSummonerIcon;
+
+const LOL_SUMMONER_URL: &str = "/lol-summoner";
+
+#[allow(unused)]
+pub enum LolSummonerEndpoint<'a> {
+    // GET /lol-summoner/v1/current-summoner -> LolSummonerSummoner
+    GetLolSummonerV1CurrentSummoner,
+    // PUT /lol-summoner/v1/current-summoner/icon -> LolSummonerSummoner
+    PutLolSummonerV1CurrentSummonerIcon(&'a LolSummonerSummonerIcon),
+    // GET /lol-summoner/v1/summoners -> LolSummonerSummoner
+    GetLolSummonerV1Summoners(&'a str, Option<bool>),
+    // GET /lol-summoner/v1/summoners/{id} -> LolSummonerSummoner
+    GetLolSummonerV1SummonersById(u64),
+}
+
+impl<'a> LolSummonerEndpoint<'a> {
+    pub fn info(&self) -> EndpointInfo {
+        match self {
+            LolSummonerEndpoint::GetLolSummonerV1CurrentSummoner => EndpointInfo {
+                url: format!("{}/v1/current-summoner", LOL_SUMMONER_URL),
+                method: Method::GET,
+                headers: None,
+                body: None,
+            },
+            LolSummonerEndpoint::PutLolSummonerV1CurrentSummonerIcon(body) => EndpointInfo {
+                url: format!("{}/v1/current-summoner/icon", LOL_SUMMONER_URL),
+                method: Method::PUT,
+                headers: None,
+                body: serde_json::to_string(body).ok(),
+            },
+            LolSummonerEndpoint::GetLolSummonerV1Summoners(name, include_icon) => EndpointInfo {
+                url: url_with_query(format!("{}/v1/summoners", LOL_SUMMONER_URL), &[("name", Some(name.to_string())), ("includeIcon", include_icon.map(|value| value.to_string()))]),
+                method: Method::GET,
+                headers: None,
+                body: None,
+            },
+            LolSummonerEndpoint::GetLolSummonerV1SummonersById(id) => EndpointInfo {
+                url: format!("{}/v1/summoners/{}", LOL_SUMMONER_URL, id),
+                method: Method::GET,
+                headers: None,
+                body: None,
+            },
+        }
+    }
+}
@@ -0,0 +1,7 @@
+// Generated by lcu_codegen from the client's API schema. Do not edit by hand.
+
+pub mod builtin;
+pub mod lol_gameflow;
+pub mod lol_settings;
+pub mod lol_summoner;
+pub mod types;
@@ -0,0 +1,52 @@
+// Generated by lcu_codegen from the client's API schema. Do not edit by hand.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LolChampSelectChampSelectSwapState {
+    #[serde(rename = "AVAILABLE")]
+    Available,
+    #[serde(rename = "IN_PROGRESS")]
+    InProgress,
+    #[serde(rename = "ACCEPTED")]
+    Accepted,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LolGameflowGameflowPhase {
+    None,
+    Lobby,
+    InProgress,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LolSummonerSummoner {
+    pub account_id: u64,
+    pub display_name: String,
+    #[serde(rename = "isRGM")]
+    pub is_rgm: bool,
+    pub reroll_points: LolSummonerSummonerRerollPoints,
+    #[serde(rename = "type")]
+    pub type_field: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LolSummonerSummonerIcon {
+    pub inventory_token: Option<String>,
+    pub profile_icon_id: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LolSummonerSummonerRerollPoints {
+    pub current_points: u32,
+    pub rolls_by_champion: HashMap<String, u32>,
+}